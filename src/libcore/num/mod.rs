@@ -336,6 +336,51 @@ pub trait Int
     #[stable(feature = "rust1", since = "1.0.0")]
     fn checked_div(self, other: Self) -> Option<Self>;
 
+    /// Wrapping integer addition. Computes `self + other`, wrapping around at
+    /// the boundary of the type.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::num::Int;
+    ///
+    /// assert_eq!(250u8.wrapping_add(10), 4);
+    /// assert_eq!(127i8.wrapping_add(1), -128);
+    /// ```
+    #[unstable(feature = "core",
+               reason = "pending integer conventions")]
+    fn wrapping_add(self, other: Self) -> Self;
+
+    /// Wrapping integer subtraction. Computes `self - other`, wrapping around
+    /// at the boundary of the type.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::num::Int;
+    ///
+    /// assert_eq!(3u8.wrapping_sub(5), 254);
+    /// assert_eq!((-128i8).wrapping_sub(1), 127);
+    /// ```
+    #[unstable(feature = "core",
+               reason = "pending integer conventions")]
+    fn wrapping_sub(self, other: Self) -> Self;
+
+    /// Wrapping integer multiplication. Computes `self * other`, wrapping
+    /// around at the boundary of the type.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::num::Int;
+    ///
+    /// assert_eq!(16u8.wrapping_mul(17), 16);
+    /// assert_eq!(64i8.wrapping_mul(2), -128);
+    /// ```
+    #[unstable(feature = "core",
+               reason = "pending integer conventions")]
+    fn wrapping_mul(self, other: Self) -> Self;
+
     /// Saturating integer addition. Computes `self + other`, saturating at
     /// the numeric bounds instead of overflowing.
     #[stable(feature = "rust1", since = "1.0.0")]
@@ -393,6 +438,13 @@ macro_rules! checked_op {
     }}
 }
 
+macro_rules! wrapping_op {
+    ($T:ty, $U:ty, $op:path, $x:expr, $y:expr) => {{
+        let (result, _) = unsafe { $op($x as $U, $y as $U) };
+        result as $T
+    }}
+}
+
 macro_rules! uint_impl {
     ($T:ty = $ActualT:ty, $BITS:expr,
      $ctpop:path,
@@ -457,6 +509,21 @@ macro_rules! uint_impl {
                 checked_op!($T, $ActualT, $mul_with_overflow, self, other)
             }
 
+            #[inline]
+            fn wrapping_add(self, other: $T) -> $T {
+                wrapping_op!($T, $ActualT, $add_with_overflow, self, other)
+            }
+
+            #[inline]
+            fn wrapping_sub(self, other: $T) -> $T {
+                wrapping_op!($T, $ActualT, $sub_with_overflow, self, other)
+            }
+
+            #[inline]
+            fn wrapping_mul(self, other: $T) -> $T {
+                wrapping_op!($T, $ActualT, $mul_with_overflow, self, other)
+            }
+
             #[inline]
             fn checked_div(self, v: $T) -> Option<$T> {
                 match v {
@@ -580,6 +647,21 @@ macro_rules! int_impl {
                 checked_op!($T, $ActualT, $mul_with_overflow, self, other)
             }
 
+            #[inline]
+            fn wrapping_add(self, other: $T) -> $T {
+                wrapping_op!($T, $ActualT, $add_with_overflow, self, other)
+            }
+
+            #[inline]
+            fn wrapping_sub(self, other: $T) -> $T {
+                wrapping_op!($T, $ActualT, $sub_with_overflow, self, other)
+            }
+
+            #[inline]
+            fn wrapping_mul(self, other: $T) -> $T {
+                wrapping_op!($T, $ActualT, $mul_with_overflow, self, other)
+            }
+
             #[inline]
             fn checked_div(self, v: $T) -> Option<$T> {
                 match v {
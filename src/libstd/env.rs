@@ -224,6 +224,45 @@ impl Error for VarError {
     }
 }
 
+/// Possible errors from the `env_typed!` macro.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ParseVarError<E> {
+    /// The environment variable could not be read, for the reason given, as
+    /// with `env::var`.
+    Var(VarError),
+
+    /// The environment variable was read, but its value could not be parsed.
+    /// The parse error is returned as a payload of this variant.
+    Parse(E),
+}
+
+impl<E: fmt::Display> fmt::Display for ParseVarError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseVarError::Var(ref e) => fmt::Display::fmt(e, f),
+            ParseVarError::Parse(ref e) => {
+                write!(f, "environment variable could not be parsed: {}", e)
+            }
+        }
+    }
+}
+
+impl<E: Error> Error for ParseVarError<E> {
+    fn description(&self) -> &str {
+        match *self {
+            ParseVarError::Var(ref e) => e.description(),
+            ParseVarError::Parse(..) => "environment variable could not be parsed",
+        }
+    }
+
+    fn cause(&self) -> Option<&Error> {
+        match *self {
+            ParseVarError::Var(ref e) => Some(e as &Error),
+            ParseVarError::Parse(ref e) => Some(e as &Error),
+        }
+    }
+}
+
 /// Sets the environment variable `k` to the value `v` for the currently running
 /// process.
 ///
@@ -38,7 +38,10 @@ pub fn on_fail(obj: &(Any+Send), file: &'static str, line: uint) {
         Some(s) => *s,
         None => match obj.downcast_ref::<String>() {
             Some(s) => &s[],
-            None => "Box<Any>",
+            None => match obj.downcast_ref::<backtrace::PanicBacktrace>() {
+                Some(p) => &p.msg[],
+                None => "Box<Any>",
+            }
         }
     };
     let mut err = Stderr;
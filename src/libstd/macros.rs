@@ -166,6 +166,3261 @@ macro_rules! select {
     })
 }
 
+/// Prints a table with a header row to the task's stdout handle.
+///
+/// The header is a bracketed list of `Display` values, and the rows are
+/// anything which can be iterated by reference to yield tuples or arrays of
+/// `Display` values (up to twelve columns). Neither the header nor the rows
+/// are consumed.
+///
+/// Each column is padded to the width of its widest cell, including the
+/// header, and the header is followed by a separator line.
+///
+/// # Example
+///
+/// ```
+/// let rows = vec![("Alice", 30), ("Bob", 4)];
+/// print_table!(["Name", "Age"]; rows);
+/// // Name  | Age
+/// // ------+----
+/// // Alice | 30
+/// // Bob   | 4
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! print_table {
+    ([$($header:expr),+]; $rows:expr) => (
+        $crate::rt::macro_support::print_table(
+            &[$($crate::string::ToString::to_string(&$header)),+],
+            $crate::iter::IntoIterator::into_iter(&$rows))
+    )
+}
+
+/// Combines several comparisons, in priority order, into one `Ordering`.
+///
+/// The comparisons are evaluated left-to-right and the first one which is not
+/// `Equal` is returned; if they are all `Equal` then so is the result. Once a
+/// comparison decides the ordering, the remaining ones are not evaluated.
+///
+/// # Example
+///
+/// ```
+/// use std::cmp::Ordering;
+///
+/// struct Version { major: u32, minor: u32, patch: u32 }
+///
+/// fn cmp_versions(a: &Version, b: &Version) -> Ordering {
+///     chained_cmp!(a.major.cmp(&b.major),
+///                  a.minor.cmp(&b.minor),
+///                  a.patch.cmp(&b.patch))
+/// }
+///
+/// let a = Version { major: 1, minor: 2, patch: 0 };
+/// let b = Version { major: 1, minor: 10, patch: 0 };
+/// assert_eq!(cmp_versions(&a, &b), Ordering::Less);
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! chained_cmp {
+    ($cmp:expr) => ($cmp);
+    ($cmp:expr, $($rest:expr),+) => (
+        match $cmp {
+            $crate::cmp::Ordering::Equal => chained_cmp!($($rest),+),
+            ordering => ordering,
+        }
+    );
+    ($($cmp:expr),+,) => (chained_cmp!($($cmp),+));
+}
+
+/// Removes the elements of a vector which match a predicate, returning them.
+///
+/// Every element for which the predicate returns `true` is moved out of the
+/// vector and into the returned `Vec`. Both the retained and the removed
+/// elements keep their relative order, and the whole operation is linear in
+/// the length of the vector.
+///
+/// The predicate is called on every element before the vector is modified,
+/// so a panicking predicate leaves the vector as it was.
+///
+/// # Example
+///
+/// ```
+/// let mut v = vec![1, 2, 3, 4, 5, 6];
+/// let evens = drain_filter!(v, |x| *x % 2 == 0);
+/// assert_eq!(v, vec![1, 3, 5]);
+/// assert_eq!(evens, vec![2, 4, 6]);
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! drain_filter {
+    ($v:expr, $pred:expr) => (
+        $crate::rt::macro_support::drain_filter(&mut $v, $pred)
+    )
+}
+
+/// Joins the `Display` forms of a list of items with a separator.
+///
+/// The items may be anything which can be iterated over, and the result is a
+/// `String` with the separator between each pair of items. No separator is
+/// added for an empty or single-element list.
+///
+/// # Example
+///
+/// ```
+/// let v = vec![1, 2, 3];
+/// assert_eq!(fmt_list!(&v, ", "), "1, 2, 3");
+/// assert_eq!(fmt_list!(v.iter().take(1), ", "), "1");
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! fmt_list {
+    ($items:expr, $sep:expr) => (
+        $crate::rt::macro_support::fmt_list($crate::iter::IntoIterator::into_iter($items),
+                                            $sep)
+    )
+}
+
+/// Declares a lazily initialized thread local key.
+///
+/// This declares a `static` of type `std::thread_local::Key<T>`, just like
+/// `thread_local!`, whose value is accessed with `NAME.with(|v| ...)`. The
+/// initializer does not run when the key is declared: it runs lazily, the
+/// first time `with` is called on each thread, so every thread sees its own
+/// freshly initialized value.
+///
+/// # Example
+///
+/// ```
+/// use std::cell::RefCell;
+///
+/// thread_local_init!(static NAMES: RefCell<Vec<String>> = RefCell::new(Vec::new());)
+///
+/// NAMES.with(|names| names.borrow_mut().push("main".to_string()));
+/// NAMES.with(|names| assert_eq!(names.borrow().len(), 1));
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! thread_local_init {
+    (static $name:ident: $t:ty = $init:expr;) => (
+        thread_local!(static $name: $t = $init);
+    );
+    (pub static $name:ident: $t:ty = $init:expr;) => (
+        thread_local!(pub static $name: $t = $init);
+    );
+}
+
+/// Panics the current thread, attaching a backtrace to the panic.
+///
+/// This takes the same format string and arguments as `panic!`, but the
+/// panic's payload is a `std::rt::backtrace::PanicBacktrace` holding both the
+/// formatted message and a backtrace of the thread taken at the panic site.
+/// Code catching the panic (for example through `JoinGuard::join`) can
+/// downcast the payload to recover both.
+///
+/// On platforms where no backtrace can be taken the backtrace is simply left
+/// empty; the panic itself behaves just like `panic!`.
+///
+/// # Example
+///
+/// ```
+/// use std::boxed::BoxAny;
+/// use std::rt::backtrace::PanicBacktrace;
+/// use std::thread::Thread;
+///
+/// let result = Thread::scoped(|| {
+///     panic_bt!("bad value: {}", 3);
+/// }).join();
+///
+/// let payload = result.err().unwrap().downcast::<PanicBacktrace>().ok().unwrap();
+/// assert_eq!(payload.msg, "bad value: 3");
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! panic_bt {
+    () => ({
+        panic_bt!("explicit panic")
+    });
+    ($($arg:tt)+) => ({
+        $crate::rt::begin_unwind_backtrace(format_args!($($arg)+), {
+            static _FILE_LINE: (&'static str, usize) = (file!(), line!());
+            &_FILE_LINE
+        })
+    });
+}
+
+/// Runs each closure on its own thread and waits for all of them.
+///
+/// Every closure is spawned with `Thread::scoped` before any of them are
+/// joined, so they all run concurrently and may borrow from the enclosing
+/// scope. The threads are then joined in order, and the result is a tuple
+/// holding each thread's `std::thread::Result`: a thread which panics yields
+/// an `Err` with its panic payload in its own slot, but does not affect the
+/// other threads. Up to twelve closures may be given.
+///
+/// # Example
+///
+/// ```
+/// let data = vec![1, 2, 3, 4];
+/// let (sum, max) = join_threads!([
+///     || data.iter().fold(0, |a, &b| a + b),
+///     || *data.iter().max().unwrap(),
+/// ]);
+/// assert_eq!(sum.ok(), Some(10));
+/// assert_eq!(max.ok(), Some(4));
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! join_threads {
+    ([$($f:expr),+]) => ({
+        $crate::rt::macro_support::JoinAll::join_all(
+            ($($crate::thread::Thread::scoped($f),)+)
+        )
+    });
+    ([$($f:expr),+,]) => (join_threads!([$($f),+]));
+}
+
+/// Zips several iterables together into a `Vec` of flat tuples.
+///
+/// Each argument may be anything implementing `IntoIterator`. The elements
+/// are paired up position by position, stopping as soon as the shortest
+/// argument runs out, and collected into a `Vec<(A, B, C, ...)>` without the
+/// `((a, b), c)` nesting of chained `zip` calls. Between two and six
+/// iterables may be given.
+///
+/// # Example
+///
+/// ```
+/// let names = vec!["x", "y", "z"];
+/// let xs = zip_collect!(names, 0..10, [true, false, true].iter());
+/// assert_eq!(xs, vec![("x", 0, &true), ("y", 1, &false), ("z", 2, &true)]);
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! zip_collect {
+    ($a:expr, $b:expr) => ({
+        $crate::iter::IntoIterator::into_iter($a)
+            .zip($crate::iter::IntoIterator::into_iter($b))
+            .collect::<$crate::vec::Vec<_>>()
+    });
+    ($a:expr, $b:expr, $c:expr) => ({
+        $crate::iter::IntoIterator::into_iter($a)
+            .zip($crate::iter::IntoIterator::into_iter($b))
+            .zip($crate::iter::IntoIterator::into_iter($c))
+            .map(|((a, b), c)| (a, b, c))
+            .collect::<$crate::vec::Vec<_>>()
+    });
+    ($a:expr, $b:expr, $c:expr, $d:expr) => ({
+        $crate::iter::IntoIterator::into_iter($a)
+            .zip($crate::iter::IntoIterator::into_iter($b))
+            .zip($crate::iter::IntoIterator::into_iter($c))
+            .zip($crate::iter::IntoIterator::into_iter($d))
+            .map(|(((a, b), c), d)| (a, b, c, d))
+            .collect::<$crate::vec::Vec<_>>()
+    });
+    ($a:expr, $b:expr, $c:expr, $d:expr, $e:expr) => ({
+        $crate::iter::IntoIterator::into_iter($a)
+            .zip($crate::iter::IntoIterator::into_iter($b))
+            .zip($crate::iter::IntoIterator::into_iter($c))
+            .zip($crate::iter::IntoIterator::into_iter($d))
+            .zip($crate::iter::IntoIterator::into_iter($e))
+            .map(|((((a, b), c), d), e)| (a, b, c, d, e))
+            .collect::<$crate::vec::Vec<_>>()
+    });
+    ($a:expr, $b:expr, $c:expr, $d:expr, $e:expr, $f:expr) => ({
+        $crate::iter::IntoIterator::into_iter($a)
+            .zip($crate::iter::IntoIterator::into_iter($b))
+            .zip($crate::iter::IntoIterator::into_iter($c))
+            .zip($crate::iter::IntoIterator::into_iter($d))
+            .zip($crate::iter::IntoIterator::into_iter($e))
+            .zip($crate::iter::IntoIterator::into_iter($f))
+            .map(|(((((a, b), c), d), e), f)| (a, b, c, d, e, f))
+            .collect::<$crate::vec::Vec<_>>()
+    });
+    ($($x:expr),+,) => (zip_collect!($($x),+));
+}
+
+/// Returns the number of characters in the `Display` form of a value.
+///
+/// This is the same as `format!("{}", x).chars().count()`, but the value is
+/// formatted into a writer which only counts characters, so no `String` is
+/// allocated. The value is borrowed, not moved. This is useful for working
+/// out column widths before printing aligned output.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(display_len!(12345), 5);
+/// assert_eq!(display_len!("héllo"), 5);
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! display_len {
+    ($x:expr) => ($crate::rt::macro_support::display_len(&$x));
+}
+
+/// Runs a closure on a new thread, giving up on it after a timeout.
+///
+/// The first argument is a `std::time::Duration`, and the second a closure
+/// which must be `Send` and `'static`. The closure is run on a newly spawned
+/// thread, and this waits for at most the given duration for it to finish,
+/// returning `Some` with its result if it did and `None` otherwise. A closure
+/// which panics also produces `None`.
+///
+/// Threads can't be killed, so after a timeout the spawned thread carries on
+/// running in the background until the closure returns; its result is then
+/// discarded.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+///
+/// let answer = with_timeout!(Duration::seconds(5), || 6 * 7);
+/// assert_eq!(answer, Some(42));
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! with_timeout {
+    ($dur:expr, $f:expr) => ($crate::rt::macro_support::with_timeout($dur, $f));
+}
+
+/// Wraps a value so that a message is printed to stderr when it's dropped.
+///
+/// `debug_print_on_drop!(name, value)` evaluates to a guard which derefs to
+/// `value`, so the value can be used through it as before. When the guard is
+/// dropped it prints `dropping name` to the task's stderr handle, which makes
+/// it easy to see exactly when (and in what order) values go away.
+///
+/// The message is only printed in debug builds; when compiled with
+/// `--cfg ndebug` the guard is silent.
+///
+/// # Example
+///
+/// ```
+/// let v = debug_print_on_drop!(v, vec![1, 2, 3]);
+/// assert_eq!(v.len(), 3);
+/// // prints "dropping v" here
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! debug_print_on_drop {
+    ($name:ident, $value:expr) => (
+        $crate::rt::macro_support::DropPrinter::new(stringify!($name), $value,
+                                                    cfg!(not(ndebug)))
+    );
+}
+
+/// Adds up one or more expressions.
+///
+/// `sum!(a, b, c)` evaluates each argument exactly once, from left to right,
+/// and is equivalent to `a + b + c`. It works with any types implementing
+/// `Add`, not just numbers, which makes it useful as a building block for
+/// other macros.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(sum!(1, 2, 3), 6);
+/// assert_eq!(sum!(0.5, 0.25), 0.75);
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! sum {
+    ($first:expr $(, $rest:expr)*) => ({
+        let acc = $first;
+        $(let acc = acc + $rest;)*
+        acc
+    });
+    ($first:expr $(, $rest:expr)*,) => (sum!($first $(, $rest)*));
+}
+
+/// Multiplies together one or more expressions.
+///
+/// `product!(a, b, c)` evaluates each argument exactly once, from left to
+/// right, and is equivalent to `a * b * c`. It works with any types
+/// implementing `Mul`.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(product!(2, 3, 4), 24);
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! product {
+    ($first:expr $(, $rest:expr)*) => ({
+        let acc = $first;
+        $(let acc = acc * $rest;)*
+        acc
+    });
+    ($first:expr $(, $rest:expr)*,) => (product!($first $(, $rest)*));
+}
+
+/// Asserts that a slice is sorted in non-decreasing order.
+///
+/// The argument may be anything which can be sliced with `[]`, such as a
+/// `Vec`, an array or a slice, and its elements must implement `PartialOrd`
+/// and `Debug`. On failure this panics with the index of the first element
+/// which is less than the one before it, along with both values.
+///
+/// # Example
+///
+/// ```
+/// let v = vec![1, 2, 2, 5];
+/// assert_sorted!(v);
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! assert_sorted {
+    ($s:expr) => ({
+        match &$s[] {
+            s => match $crate::rt::macro_support::first_unsorted(s, |a, b| a <= b) {
+                Some(i) => panic!("assertion failed: `{}` is not sorted: element {} (`{:?}`) \
+                                   is out of order after `{:?}`",
+                                  stringify!($s), i, s[i], s[i - 1]),
+                None => {}
+            }
+        }
+    })
+}
+
+/// Asserts that a slice is sorted according to a comparator.
+///
+/// The comparator is called with references to each pair of adjacent
+/// elements and returns an `Ordering`, as with `sort_by`; the slice is sorted
+/// if it never returns `Greater`. Otherwise this panics like `assert_sorted!`.
+///
+/// # Example
+///
+/// ```
+/// let words = ["a", "bb", "cc", "ddd"];
+/// assert_sorted_by!(words, |a, b| a.len().cmp(&b.len()));
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! assert_sorted_by {
+    ($s:expr, $cmp:expr) => ({
+        match &$s[] {
+            s => match $crate::rt::macro_support::first_unsorted_by(s, $cmp) {
+                Some(i) => panic!("assertion failed: `{}` is not sorted: element {} (`{:?}`) \
+                                   is out of order after `{:?}`",
+                                  stringify!($s), i, s[i], s[i - 1]),
+                None => {}
+            }
+        }
+    })
+}
+
+/// Asserts that a slice is sorted in non-increasing order.
+///
+/// This is the descending counterpart of `assert_sorted!`.
+///
+/// # Example
+///
+/// ```
+/// assert_sorted_desc!([9, 4, 4, 1]);
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! assert_sorted_desc {
+    ($s:expr) => ({
+        match &$s[] {
+            s => match $crate::rt::macro_support::first_unsorted(s, |a, b| a >= b) {
+                Some(i) => panic!("assertion failed: `{}` is not sorted in descending order: \
+                                   element {} (`{:?}`) is out of order after `{:?}`",
+                                  stringify!($s), i, s[i], s[i - 1]),
+                None => {}
+            }
+        }
+    })
+}
+
+/// Temporarily sets a place to a new value while running a block.
+///
+/// `scoped_set!(place, value, { ... })` moves `value` into `place`, runs the
+/// block, and then moves the original value back into `place`, evaluating to
+/// the block's value. The original value is restored even if the block
+/// panics, so this is convenient for overriding configuration (or a
+/// `static mut`) for the duration of a test. Any type of value may be used,
+/// not only `Copy` ones; whatever is in the place when the block finishes is
+/// dropped.
+///
+/// The place may be used freely within the block.
+///
+/// # Example
+///
+/// ```
+/// let mut verbose = false;
+/// let answer = scoped_set!(verbose, true, {
+///     assert!(verbose);
+///     42
+/// });
+/// assert!(!verbose);
+/// assert_eq!(answer, 42);
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! scoped_set {
+    ($place:expr, $value:expr, $body:block) => ({
+        // The unsafe block lives in an inner function so that it's not
+        // reported as unnecessary when the macro is used in an unsafe block
+        // (as it must be for a `static mut`).
+        fn restore<T>(place: *mut T, value: T) -> $crate::rt::macro_support::Restore<T> {
+            unsafe { $crate::rt::macro_support::Restore::new(place, value) }
+        }
+        let value = $value;
+        let place: *mut _ = &mut $place;
+        let _restore = restore(place, value);
+        $body
+    });
+}
+
+/// Chains one or more iterables together into a single iterator.
+///
+/// Each argument may be anything implementing `IntoIterator`, as long as they
+/// all yield the same item type. The resulting iterator yields all of the
+/// first argument's items, then all of the second's, and so on. Nothing is
+/// collected along the way: the iterator is as lazy as the ones it chains.
+///
+/// # Example
+///
+/// ```
+/// let v = chain!(vec![1, 2], 3..5, Some(5).into_iter()).collect::<Vec<_>>();
+/// assert_eq!(v, vec![1, 2, 3, 4, 5]);
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! chain {
+    ($first:expr $(, $rest:expr)*) => (
+        $crate::iter::IntoIterator::into_iter($first)
+            $(.chain($crate::iter::IntoIterator::into_iter($rest)))*
+    );
+    ($first:expr $(, $rest:expr)*,) => (chain!($first $(, $rest)*));
+}
+
+/// Prints a hexdump of some bytes to the task's stderr handle.
+///
+/// The argument can be anything which slices to a `[u8]`, such as a byte
+/// string, a `Vec<u8>` or a `&[u8]`; it is only borrowed. The output is in the
+/// classic `hexdump -C` layout: each row holds the offset of its first byte,
+/// sixteen bytes in hex with an extra space after the eighth, and the same
+/// bytes as ASCII, with non-printable bytes shown as `.`.
+///
+/// # Example
+///
+/// ```
+/// hexdump!(b"Hello, world!\n");
+/// // 00000000  48 65 6c 6c 6f 2c 20 77  6f 72 6c 64 21 0a        |Hello, world!.|
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! hexdump {
+    ($bytes:expr) => ($crate::rt::macro_support::hexdump(&$bytes[]));
+}
+
+/// Polls a condition until it holds or a timeout elapses.
+///
+/// `retry_until!(timeout, interval, || cond)` calls the closure, and while it
+/// returns `false` sleeps for `interval` and tries again, until `timeout` has
+/// passed since the first call. Both durations are `std::time::Duration`s.
+/// It evaluates to `true` as soon as the closure does, or to `false` if the
+/// timeout is reached first. The condition is checked one last time when the
+/// timeout expires, and always at least once.
+///
+/// This is mostly useful in tests which need to wait on another thread.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::atomic::{AtomicBool, Ordering, ATOMIC_BOOL_INIT};
+/// use std::thread::Thread;
+/// use std::time::Duration;
+///
+/// static READY: AtomicBool = ATOMIC_BOOL_INIT;
+///
+/// Thread::spawn(|| READY.store(true, Ordering::SeqCst));
+/// assert!(retry_until!(Duration::seconds(5), Duration::milliseconds(10),
+///                      || READY.load(Ordering::SeqCst)));
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! retry_until {
+    ($timeout:expr, $interval:expr, $cond:expr) => (
+        $crate::rt::macro_support::retry_until($timeout, $interval, $cond)
+    );
+}
+
+/// Clamps a value to an inclusive range.
+///
+/// `clamp!(x, lo, hi)` evaluates to `lo` if `x < lo`, to `hi` if `x > hi`,
+/// and to `x` otherwise. The arguments only need to implement `PartialOrd`,
+/// and each is evaluated exactly once, in order.
+///
+/// It is a logic error for `lo` to be greater than `hi`; this is checked with
+/// `debug_assert!`. Without the check, such a call evaluates to `lo` when
+/// `x < lo`, and to `hi` otherwise.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(clamp!(15, 0, 10), 10);
+/// assert_eq!(clamp!(-0.5, 0.0, 1.0), 0.0);
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! clamp {
+    ($x:expr, $lo:expr, $hi:expr) => ({
+        match ($x, $lo, $hi) {
+            (x, lo, hi) => {
+                debug_assert!(!(hi < lo), "clamp! called with a lower bound above the upper bound");
+                if x < lo { lo } else if hi < x { hi } else { x }
+            }
+        }
+    });
+}
+
+/// Computes the byte offset of a field within a struct.
+///
+/// `offset_of!(Type, field)` evaluates to a `usize` giving the offset of
+/// `field` from the start of a value of `Type`. A path of nested fields, as in
+/// `offset_of!(Type, a.b)`, gives the offset of `b` from the start of the
+/// outer struct; each field along the path must be stored inline, not behind
+/// a pointer such as a `Box`.
+///
+/// The layout of a struct is only guaranteed for `#[repr(C)]` types; for
+/// other types the offset may change from one compilation to the next.
+///
+/// The type must be sized. Layout is not known when macros are expanded, so
+/// the offset is not a constant expression: it is computed at run time (from
+/// the address of the field in a value which is never read), and cannot be
+/// used in a `const`, a `static` or an array length. It is still optimized
+/// down to a constant. The type must not mention generic parameters of the
+/// enclosing function.
+///
+/// # Example
+///
+/// ```
+/// #[repr(C)]
+/// struct Header {
+///     tag: u8,
+///     len: u32,
+/// }
+///
+/// assert_eq!(offset_of!(Header, tag), 0);
+/// assert_eq!(offset_of!(Header, len), 4);
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! offset_of {
+    ($t:ty, $($field:ident).+) => ({
+        // This is an inner function (which can't see any enclosing generic
+        // parameters) so that its unsafe block is not reported as
+        // unnecessary when the macro is used in an unsafe block.
+        #[inline(always)]
+        fn offset() -> usize {
+            // There is no value of the type to hand, so this takes a made-up
+            // address for one, chosen to be non-null and suitably aligned.
+            // `(*base).field` only names the field's place, to take its
+            // address: the place is never read or written, so nothing is
+            // ever loaded from this address, and no value of the type is
+            // created or dropped. The fields must all be stored inline, so
+            // that no pointer is followed on the way to the last one.
+            let base = $crate::mem::align_of::<$t>() as *const $t;
+            let field = unsafe { &(*base).$($field).+ as *const _ as usize };
+            field - base as usize
+        }
+        offset()
+    });
+}
+
+/// Formats a byte count as a human-readable size.
+///
+/// `format_si!(bytes)` returns a `String` such as `"1.5 KiB"` or
+/// `"3.2 MiB"`, using the largest binary (base 1024) unit of which there is
+/// at least one and one decimal place. Counts below one kibibyte are given
+/// exactly in bytes, as in `"512 B"`. `format_si!(bytes, 1000)` uses decimal
+/// SI units (`kB`, `MB`, ...) instead. The count is converted to a `u64`.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(format_si!(1536), "1.5 KiB");
+/// assert_eq!(format_si!(3_200_000, 1000), "3.2 MB");
+/// assert_eq!(format_si!(100), "100 B");
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! format_si {
+    ($bytes:expr) => (format_si!($bytes, 1024));
+    ($bytes:expr, $base:expr) => (
+        $crate::rt::macro_support::format_si($bytes as u64, $base)
+    );
+}
+
+/// Collects an iterator of `Result`s into a `Result` of a `Vec`.
+///
+/// The argument may be anything implementing `IntoIterator` with an item type
+/// of `Result<T, E>`. Items are collected in order into a `Vec<T>`, presized
+/// from the iterator's size hint, and `Ok` of that vector is returned.
+/// However, as soon as an `Err` is seen it is returned instead, and nothing
+/// more is taken from the iterator.
+///
+/// # Example
+///
+/// ```
+/// let nums = try_collect!("1 2 3".split(' ').map(|s| s.parse::<i32>()));
+/// assert_eq!(nums, Ok(vec![1, 2, 3]));
+///
+/// let bad = try_collect!("1 x 3".split(' ').map(|s| s.parse::<i32>()));
+/// assert!(bad.is_err());
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! try_collect {
+    ($iter:expr) => (
+        $crate::rt::macro_support::try_collect($crate::iter::IntoIterator::into_iter($iter))
+    );
+}
+
+/// Computes the largest of two or more integer constant expressions.
+///
+/// Unlike `cmp::max`, this can be used where a constant is required, such as
+/// in a `const` or the length of an array. All of the arguments must have
+/// the same integer type.
+///
+/// Constant expressions can't contain branches, so this is computed with
+/// arithmetic alone, and each argument is expanded several times; it is
+/// intended for constants, not for expressions with side effects.
+///
+/// # Example
+///
+/// ```
+/// const SMALL: usize = 16;
+/// const LARGE: usize = 64;
+///
+/// static BUF: [u8; const_max!(SMALL, LARGE, 32)] = [0; const_max!(SMALL, LARGE, 32)];
+/// assert_eq!(BUF.len(), 64);
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! const_max {
+    ($a:expr, $b:expr) => (
+        // `$a + ($b - $a) * f`, where `f` is 1 if `$a < $b` and 0 otherwise,
+        // computed as `(1 << ($a < $b) as u8) - 1` in the type of `$a`.
+        ($a) + (($b) - ($a)) *
+            (((($a) - ($a) + 1) << ((($a) < ($b)) as u8)) - (($a) - ($a) + 1))
+    );
+    ($a:expr, $b:expr, $($rest:expr),+) => (
+        const_max!(const_max!($a, $b), $($rest),+)
+    );
+}
+
+/// Computes the smallest of two or more integer constant expressions.
+///
+/// This is the counterpart of `const_max!`, and has the same restrictions.
+///
+/// # Example
+///
+/// ```
+/// const LIMIT: u32 = 100;
+/// const MIN: u32 = const_min!(LIMIT, 250, 120);
+/// assert_eq!(MIN, 100);
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! const_min {
+    ($a:expr, $b:expr) => (
+        // `$a + ($b - $a) * f`, where `f` is 1 if `$b < $a` and 0 otherwise,
+        // computed as `(1 << ($b < $a) as u8) - 1` in the type of `$a`.
+        ($a) + (($b) - ($a)) *
+            (((($a) - ($a) + 1) << ((($b) < ($a)) as u8)) - (($a) - ($a) + 1))
+    );
+    ($a:expr, $b:expr, $($rest:expr),+) => (
+        const_min!(const_min!($a, $b), $($rest),+)
+    );
+}
+
+/// Formats a `Duration` as a compact, human-readable `String`.
+///
+/// Durations of a millisecond or more are written as hours, minutes and
+/// seconds, with the seconds given to the millisecond, as in `1h2m3.004s`.
+/// Leading components which are zero are left out, so three and a half
+/// seconds is `3.500s`, and half a second is `0.500s`. Shorter durations are
+/// written in whole microseconds (`250us`), or nanoseconds below that
+/// (`40ns`). Negative durations are prefixed with `-`.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+///
+/// let d = Duration::minutes(2) + Duration::milliseconds(1500);
+/// assert_eq!(fmt_duration!(d), "2m1.500s");
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! fmt_duration {
+    ($d:expr) => ($crate::rt::macro_support::fmt_duration($d));
+}
+
+/// Prints a `Duration` to the task's stderr handle, followed by a newline.
+///
+/// The duration is formatted as with `fmt_duration!`.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+///
+/// print_duration!(Duration::span(|| {
+///     // some work
+/// }));
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! print_duration {
+    ($d:expr) => (
+        $crate::old_io::stdio::eprintln_args(
+            format_args!("{}", $crate::rt::macro_support::fmt_duration($d)))
+    );
+}
+
+/// Converts several integers to big endian at once.
+///
+/// `to_be!(a, b, c)` evaluates to the tuple `(a.to_be(), b.to_be(),
+/// c.to_be())`, evaluating each argument exactly once. Each argument may be of
+/// a different integer type. A single argument gives a one-element tuple.
+///
+/// # Example
+///
+/// ```
+/// let (kind, len) = to_be!(1u16, 0x01020304u32);
+/// assert_eq!(kind, 1u16.to_be());
+/// assert_eq!(len, 0x01020304u32.to_be());
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! to_be {
+    ($($x:expr),+) => (($($crate::num::Int::to_be($x),)+));
+    ($($x:expr),+,) => (to_be!($($x),+));
+}
+
+/// Converts several integers to little endian at once.
+///
+/// This is the little endian counterpart of `to_be!`.
+///
+/// # Example
+///
+/// ```
+/// let (kind, len) = to_le!(1u16, 0x01020304u32);
+/// assert_eq!(kind, 1u16.to_le());
+/// assert_eq!(len, 0x01020304u32.to_le());
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! to_le {
+    ($($x:expr),+) => (($($crate::num::Int::to_le($x),)+));
+    ($($x:expr),+,) => (to_le!($($x),+));
+}
+
+/// Declares lazily initialized statics.
+///
+/// `lazy_static!(static ref NAME: Type = init;)` declares a static `NAME`
+/// which dereferences to a `&'static Type`. The initializer is run the first
+/// time `NAME` is dereferenced, from whichever thread gets there first, and
+/// never again: threads which race to the first access block until it has
+/// finished, and every access sees the same value. As the value is shared
+/// between threads, `Type` must be `Sync`.
+///
+/// Several statics may be declared in one invocation, and each may be `pub`
+/// and carry attributes (such as doc comments). The value is never dropped.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// lazy_static! {
+///     static ref NAMES: HashMap<u32, &'static str> = {
+///         let mut m = HashMap::new();
+///         m.insert(0, "zero");
+///         m.insert(1, "one");
+///         m
+///     };
+/// }
+///
+/// assert_eq!(NAMES.get(&1), Some(&"one"));
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! lazy_static {
+    ($(#[$attr:meta])* static ref $name:ident : $t:ty = $init:expr; $($rest:tt)*) => (
+        lazy_static!(@item [$(#[$attr])*] [] $name, $t, $init);
+        lazy_static!($($rest)*);
+    );
+    ($(#[$attr:meta])* pub static ref $name:ident : $t:ty = $init:expr; $($rest:tt)*) => (
+        lazy_static!(@item [$(#[$attr])*] [pub] $name, $t, $init);
+        lazy_static!($($rest)*);
+    );
+    (@item [$(#[$attr:meta])*] [$($vis:tt)*] $name:ident, $t:ty, $init:expr) => (
+        #[allow(non_camel_case_types)]
+        #[allow(dead_code)]
+        $($vis)* struct $name { __private_field: () }
+
+        $(#[$attr])*
+        $($vis)* static $name: $name = $name { __private_field: () };
+
+        impl $crate::ops::Deref for $name {
+            type Target = $t;
+
+            fn deref<'a>(&'a self) -> &'a $t {
+                use $crate::sync::{Once, ONCE_INIT};
+
+                // The initializer is evaluated out here, rather than within
+                // the unsafe block below.
+                #[inline(always)]
+                fn init() -> $crate::boxed::Box<$t> { $crate::boxed::Box::new($init) }
+                #[inline(always)]
+                fn require_sync<T: Sync>(_: &T) {}
+
+                static ONCE: Once = ONCE_INIT;
+                static mut VALUE: *const $t = 0 as *const $t;
+                unsafe {
+                    ONCE.call_once(|| VALUE = $crate::mem::transmute(init()));
+                    let value = &*VALUE;
+                    require_sync(value);
+                    value
+                }
+            }
+        }
+    );
+    () => ();
+}
+
+/// Receives a message from a channel, waiting for at most a given time.
+///
+/// `recv_timeout!(rx, duration)` blocks on the `mpsc::Receiver` `rx` (which
+/// is only borrowed) for up to the `std::time::Duration` `duration`. It
+/// evaluates to a `Result<Option<T>, RecvError>`:
+///
+/// * `Ok(Some(msg))` if a message was received in time;
+/// * `Ok(None)` if the timeout elapsed first;
+/// * `Err(RecvError)` if the sending half has hung up and there are no more
+///   messages to receive, just as for `Receiver::recv`.
+///
+/// This is a shorthand for a `select!` between the receiver and a timer.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::mpsc::channel;
+/// use std::time::Duration;
+///
+/// let (tx, rx) = channel();
+/// tx.send(1).unwrap();
+/// assert_eq!(recv_timeout!(rx, Duration::seconds(1)), Ok(Some(1)));
+/// assert_eq!(recv_timeout!(rx, Duration::milliseconds(10)), Ok(None));
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! recv_timeout {
+    ($rx:expr, $dur:expr) => ($crate::rt::macro_support::recv_timeout(&$rx, $dur));
+}
+
+/// Assigns several fields of a struct in place.
+///
+/// `struct_update!(place, { a: x, b: y })` is shorthand for `place.a = x;
+/// place.b = y;`. The values are evaluated exactly once each, in the order
+/// they are listed, and each is assigned before the next is evaluated, so
+/// later values may refer to fields assigned earlier. The place itself is
+/// named once per field, so it should be a simple place such as a variable,
+/// a field, or a dereferenced `&mut`.
+///
+/// # Example
+///
+/// ```
+/// struct Point { x: i32, y: i32 }
+///
+/// let mut p = Point { x: 1, y: 2 };
+/// struct_update!(p, { x: 10, y: p.x * 2 });
+/// assert_eq!((p.x, p.y), (10, 20));
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! struct_update {
+    ($place:expr, { $($field:ident: $value:expr),* }) => ({
+        $($place.$field = $value;)*
+    });
+    ($place:expr, { $($field:ident: $value:expr),+, }) => (
+        struct_update!($place, { $($field: $value),+ })
+    );
+}
+
+/// Times a closure over a number of iterations.
+///
+/// `benchmark!("label", n, || work())` calls the closure `n` times, which must
+/// be at least one, and measures the total time taken. It then prints
+/// `label: n iters, x ns/iter` to the task's stderr handle and evaluates to
+/// the mean time per iteration in nanoseconds, as an `f64`. The closure's
+/// result is passed through a function the optimizer can't see into, so that
+/// the work can't be optimized away.
+///
+/// This is meant for quick measurements outside of the test harness; for
+/// anything more careful, use `#[bench]`.
+///
+/// # Example
+///
+/// ```
+/// let ns = benchmark!("sum", 1000, || (0..100u64).fold(0, |a, b| a + b));
+/// assert!(ns >= 0.0);
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! benchmark {
+    ($label:expr, $iters:expr, $f:expr) => (
+        $crate::rt::macro_support::benchmark($label, $iters as u64, $f)
+    );
+}
+
+/// Maps a closure over an iterable and collects the results.
+///
+/// `map_collect!(iter, |x| f(x))` is shorthand for
+/// `iter.into_iter().map(|x| f(x)).collect::<Vec<_>>()`; as with `collect`,
+/// the vector is presized from the iterator's size hint. A different
+/// collection can be built by naming its type after a `=>`, as in
+/// `map_collect!(iter, |x| f(x) => HashSet<_>)`; any type implementing
+/// `FromIterator` will do.
+///
+/// # Example
+///
+/// ```
+/// let squares = map_collect!(1..4, |x| x * x);
+/// assert_eq!(squares, vec![1, 4, 9]);
+///
+/// let word = map_collect!(vec!['r', 'u', 's', 't'], |c| c.to_uppercase() => String);
+/// assert_eq!(word, "RUST");
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! map_collect {
+    ($iter:expr, $f:expr => $t:ty) => (
+        $crate::iter::IntoIterator::into_iter($iter).map($f).collect::<$t>()
+    );
+    ($iter:expr, $f:expr) => (
+        $crate::iter::IntoIterator::into_iter($iter).map($f).collect::<$crate::vec::Vec<_>>()
+    );
+}
+
+/// Declares a tuple struct wrapping a single value, with delegating impls.
+///
+/// The grammar is
+///
+/// ```text
+/// newtype!([pub] struct Name(Inner)[: Trait, ...];)
+/// ```
+///
+/// where the struct may be preceded by attributes such as `#[derive]`. This
+/// declares `struct Name(Inner);`, with a private field, and then an
+/// implementation of each listed trait which delegates to the field. The
+/// traits which may be listed are:
+///
+/// * `Display` and `Debug`, which format the inner value;
+/// * `Deref` and `DerefMut`, which dereference to the inner value;
+/// * `From`, which adds an inherent `Name::from(Inner)` constructor (there is
+///   no standard conversion trait for it to implement).
+///
+/// # Example
+///
+/// ```
+/// newtype!(struct Meters(f64): Display, Deref, From;);
+///
+/// let m = Meters::from(1.5);
+/// assert_eq!(format!("{}", m), "1.5");
+/// assert_eq!(*m * 2.0, 3.0);
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! newtype {
+    ($(#[$attr:meta])* pub struct $name:ident($inner:ty) $(: $($tr:ident),+)*;) => (
+        $(#[$attr])*
+        pub struct $name($inner);
+        $($(newtype!(@impl $name, $inner, $tr);)+)*
+    );
+    ($(#[$attr:meta])* struct $name:ident($inner:ty) $(: $($tr:ident),+)*;) => (
+        $(#[$attr])*
+        struct $name($inner);
+        $($(newtype!(@impl $name, $inner, $tr);)+)*
+    );
+    (@impl $name:ident, $inner:ty, Display) => (
+        impl $crate::fmt::Display for $name {
+            fn fmt(&self, f: &mut $crate::fmt::Formatter) -> $crate::fmt::Result {
+                $crate::fmt::Display::fmt(&self.0, f)
+            }
+        }
+    );
+    (@impl $name:ident, $inner:ty, Debug) => (
+        impl $crate::fmt::Debug for $name {
+            fn fmt(&self, f: &mut $crate::fmt::Formatter) -> $crate::fmt::Result {
+                $crate::fmt::Debug::fmt(&self.0, f)
+            }
+        }
+    );
+    (@impl $name:ident, $inner:ty, Deref) => (
+        impl $crate::ops::Deref for $name {
+            type Target = $inner;
+            fn deref(&self) -> &$inner { &self.0 }
+        }
+    );
+    (@impl $name:ident, $inner:ty, DerefMut) => (
+        impl $crate::ops::DerefMut for $name {
+            fn deref_mut(&mut self) -> &mut $inner { &mut self.0 }
+        }
+    );
+    (@impl $name:ident, $inner:ty, From) => (
+        impl $name {
+            /// Wraps a value.
+            pub fn from(value: $inner) -> $name { $name(value) }
+        }
+    );
+}
+
+/// Prints the size and alignment of several types to the task's stderr handle.
+///
+/// One line is printed for each type, in the form `name: size N, align M`,
+/// where the name is the type as written, and the numbers are those given by
+/// `mem::size_of` and `mem::align_of`.
+///
+/// # Example
+///
+/// ```
+/// struct Point { x: f32, y: f32 }
+///
+/// print_type_sizes!(u8, Point, Option<Box<Point>>);
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! print_type_sizes {
+    ($($t:ty),+) => ({
+        $(
+            $crate::old_io::stdio::eprintln_args(
+                format_args!("{}: size {}, align {}", stringify!($t),
+                             $crate::mem::size_of::<$t>(),
+                             $crate::mem::align_of::<$t>()));
+        )+
+    });
+    ($($t:ty),+,) => (print_type_sizes!($($t),+));
+}
+
+/// Folds an iterable with an accumulator, from the left.
+///
+/// `fold!(iter, init, |acc, x| expr)` is the same as
+/// `iter.into_iter().fold(init, |acc, x| expr)`, and any `FnMut(B, A) -> B`
+/// may be given in place of the closure.
+///
+/// The body may also be written as a block, after names for the accumulator
+/// and the element: `fold!(iter, init, acc, x => { ...; acc })`. The block is
+/// evaluated once for each element, and its value becomes the new
+/// accumulator. The accumulator may be declared as `mut acc`.
+///
+/// # Example
+///
+/// ```
+/// let v = vec![1, 2, 3, 4];
+/// assert_eq!(fold!(v.iter(), 0, |acc, &x| acc + x), 10);
+///
+/// let s = fold!(v, String::new(), mut acc, x => {
+///     acc.push_str(&x.to_string()[]);
+///     acc
+/// });
+/// assert_eq!(s, "1234");
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! fold {
+    ($iter:expr, $init:expr, mut $acc:ident, $x:pat => $body:block) => (
+        $crate::iter::IntoIterator::into_iter($iter).fold($init, |mut $acc, $x| $body)
+    );
+    ($iter:expr, $init:expr, $acc:ident, $x:pat => $body:block) => (
+        $crate::iter::IntoIterator::into_iter($iter).fold($init, |$acc, $x| $body)
+    );
+    ($iter:expr, $init:expr, $f:expr) => (
+        $crate::iter::IntoIterator::into_iter($iter).fold($init, $f)
+    );
+}
+
+/// Adds up one or more integer constant expressions.
+///
+/// Unlike `sum!`, this expands to a single expression with no `let`
+/// bindings, so when every argument is a constant the result is a constant
+/// too, and may be used as an array length or in a `const` or `static`.
+///
+/// # Example
+///
+/// ```
+/// const HEADER: usize = 4;
+/// const LEN: usize = const_sum!(HEADER, 2, 8);
+///
+/// static PACKET: [u8; const_sum!(HEADER, 2, 8)] = [0; LEN];
+/// assert_eq!(PACKET.len(), 14);
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! const_sum {
+    ($first:expr $(, $rest:expr)*) => ((($first) $(+ ($rest))*));
+    ($first:expr $(, $rest:expr)*,) => (const_sum!($first $(, $rest)*));
+}
+
+/// Prints a line to the task's stderr handle, in debug builds only.
+///
+/// In a debug build, `dprintln!("fmt", args...)` formats its arguments as
+/// `println!` does and writes the line to stderr, prefixed with the file and
+/// line of the invocation, as in `[src/main.rs:10] x = 5`. When compiling
+/// with `--cfg ndebug` the whole invocation is removed, as with
+/// `debug_only!`: the arguments are neither evaluated nor compiled.
+///
+/// # Example
+///
+/// ```
+/// let x = 5;
+/// dprintln!("x = {}", x);
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! dprintln {
+    ($fmt:expr) => (dprintln!($fmt,));
+    ($fmt:expr, $($arg:tt)*) => (
+        debug_only!({
+            $crate::old_io::stdio::eprintln_args(
+                format_args!(concat!("[{}:{}] ", $fmt), file!(), line!(), $($arg)*))
+        })
+    );
+}
+
+/// Unwraps an `Option`, or gives the default value on `None`.
+///
+/// `or_default!(opt)` evaluates to the value inside `opt`, or to
+/// `Default::default()` if it is `None`. The type of the default may be given
+/// as a second argument, as `or_default!(opt, Type)`, for when it can't be
+/// inferred.
+///
+/// # Example
+///
+/// ```
+/// let name: Option<String> = None;
+/// assert_eq!(or_default!(name), "");
+///
+/// assert_eq!(or_default!("42".parse().ok(), u32), 42);
+/// assert_eq!(or_default!("x".parse().ok(), u32), 0);
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! or_default {
+    ($opt:expr) => (
+        match $opt {
+            $crate::option::Option::Some(v) => v,
+            $crate::option::Option::None => $crate::default::Default::default(),
+        }
+    );
+    ($opt:expr, $t:ty) => (
+        match $opt {
+            $crate::option::Option::Some(v) => v,
+            $crate::option::Option::None => {
+                let d: $t = $crate::default::Default::default();
+                d
+            }
+        }
+    );
+}
+
+/// Splits a string in two at the first occurrence of a delimiter.
+///
+/// `kv_split!(s, delim)` evaluates to `Some((key, value))`, where `key` is the
+/// part of `s` before the first `delim` and `value` the part after it, or to
+/// `None` if `delim` doesn't occur in `s`. The delimiter may be a `char` or a
+/// `&str`, and is not included in either part. Both parts borrow from `s`, so
+/// nothing is allocated.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(kv_split!("name=value", '='), Some(("name", "value")));
+/// assert_eq!(kv_split!("a := b := c", " := "), Some(("a", "b := c")));
+/// assert_eq!(kv_split!("flag", '='), None);
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! kv_split {
+    ($s:expr, $delim:expr) => ($crate::rt::macro_support::kv_split(&$s[], $delim));
+}
+
+/// Makes a deadline, the given `Duration` from now, for `sleep_until!`.
+///
+/// The deadline is measured on the monotonic clock, so it isn't affected by
+/// changes to the system time.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+///
+/// let deadline = deadline!(Duration::milliseconds(10));
+/// sleep_until!(deadline);
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! deadline {
+    ($after:expr) => ($crate::rt::macro_support::Deadline::after($after));
+}
+
+/// Puts the current task to sleep until a deadline made by `deadline!`.
+///
+/// The time remaining is worked out afresh each time the task wakes, so the
+/// task sleeps until the deadline itself, however long it took to get here.
+/// If the deadline has already passed this returns immediately. The same
+/// deadline may be waited for any number of times.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+///
+/// let deadline = deadline!(Duration::milliseconds(20));
+/// for _ in 0..3 {
+///     // some work
+/// }
+/// sleep_until!(deadline);
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! sleep_until {
+    ($deadline:expr) => ($crate::rt::macro_support::sleep_until(&$deadline));
+}
+
+/// Runs a block when the enclosing scope is left by a panic.
+///
+/// `defer_on_unwind!({ ... });` is a statement. If the scope it appears in is
+/// later left because the thread is panicking, the block is run as the stack
+/// unwinds past it, in the same order as other destructors. If the scope is
+/// left normally, the block is thrown away without being run. The block
+/// borrows whatever it uses until the end of the scope.
+///
+/// A guard made while the thread is already panicking, such as in a
+/// destructor run during unwinding, never runs its block, since leaving its
+/// scope isn't caused by a new panic. A panic from inside the block itself
+/// would be a panic during unwinding, which aborts the process.
+///
+/// # Example
+///
+/// ```
+/// use std::cell::Cell;
+///
+/// let committed = Cell::new(false);
+/// {
+///     defer_on_unwind!({ println!("rolling back"); });
+///     committed.set(true);
+/// }
+/// // the scope ended normally, so nothing was printed
+/// assert!(committed.get());
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! defer_on_unwind {
+    ($body:block) => (
+        let _guard = $crate::rt::macro_support::UnwindGuard::new(|| $body);
+    );
+}
+
+/// Makes an iterator which counts off a number of steps.
+///
+/// `times!(n)` yields the indices `0`, `1`, ... up to but not including `n`,
+/// so `for _ in times!(n)` runs its body `n` times. `times!(n, f)` yields
+/// `f(0)`, `f(1)`, ... instead, for any `FnMut` taking an index. The indices
+/// have the same type as `n`.
+///
+/// # Example
+///
+/// ```
+/// let mut count = 0;
+/// for _ in times!(3) {
+///     count += 1;
+/// }
+/// assert_eq!(count, 3);
+///
+/// let squares = times!(4, |i| i * i).collect::<Vec<_>>();
+/// assert_eq!(squares, vec![0, 1, 4, 9]);
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! times {
+    ($n:expr) => (0..$n);
+    ($n:expr, $f:expr) => ($crate::iter::IteratorExt::map(0..$n, $f));
+}
+
+/// Spawns a thread pinned to a particular CPU.
+///
+/// `spawn_on_cpu!(cpu, { ... })` spawns a thread which sets its CPU affinity
+/// to the CPU numbered `cpu` (counting from zero) and then runs the block,
+/// evaluating to a `JoinGuard` for it, as `Thread::scoped` does. Joining the
+/// guard gives the value of the block. The block is a `move` closure.
+///
+/// Pinning is best-effort. It is supported on Linux and Windows; on other
+/// platforms, or if the CPU doesn't exist or setting the affinity otherwise
+/// fails, the thread simply runs unpinned.
+///
+/// # Example
+///
+/// ```
+/// let guard = spawn_on_cpu!(0, {
+///     (0..1000).fold(0u64, |a, b| a + b)
+/// });
+/// assert_eq!(guard.join().ok(), Some(499500));
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! spawn_on_cpu {
+    ($cpu:expr, $body:block) => (
+        $crate::rt::macro_support::spawn_on_cpu($cpu, move || $body)
+    );
+}
+
+/// Converts an integer to another integer type, with a fallback.
+///
+/// `try_into_or!(value, default)` converts `value` to the type of `default`.
+/// If `value` is in range of that type this evaluates to the converted value,
+/// and otherwise to `default`, which is only evaluated in that case.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(try_into_or!(200i64, 0u8), 200);
+/// assert_eq!(try_into_or!(-1i64, 0u8), 0);
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! try_into_or {
+    ($value:expr, $default:expr) => (
+        match $crate::num::NumCast::from($value) {
+            $crate::option::Option::Some(v) => v,
+            $crate::option::Option::None => $default,
+        }
+    );
+}
+
+/// Converts an integer to another integer type, clamping it to the range of
+/// that type.
+///
+/// Values which are too small for the target type become its minimum value,
+/// and values which are too large become its maximum. The target type is
+/// inferred, or may be given after the value, as `try_into_clamp!(v, u8)`.
+///
+/// # Example
+///
+/// ```
+/// let small: u8 = try_into_clamp!(300i64);
+/// assert_eq!(small, 255);
+/// assert_eq!(try_into_clamp!(-5i32, u16), 0);
+/// assert_eq!(try_into_clamp!(-5i32, i8), -5);
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! try_into_clamp {
+    ($value:expr) => ($crate::rt::macro_support::clamp_cast($value));
+    ($value:expr, $t:ty) => ($crate::rt::macro_support::clamp_cast::<_, $t>($value));
+}
+
+/// Groups the elements of an iterable into a `HashMap` of vectors.
+///
+/// `group_by!(items, |x| key)` calls the closure with a reference to each
+/// element in turn, and collects the elements into a
+/// `HashMap<K, Vec<T>>`, with one vector for each distinct key. Within each
+/// vector the elements are in the order they came from `items`. The key type
+/// must implement `Hash` and `Eq`.
+///
+/// # Example
+///
+/// ```
+/// let groups = group_by!(vec![1, 2, 3, 4, 5], |x| *x % 2 == 0);
+/// assert_eq!(groups[true], vec![2, 4]);
+/// assert_eq!(groups[false], vec![1, 3, 5]);
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! group_by {
+    ($items:expr, $key:expr) => (
+        $crate::rt::macro_support::group_by($crate::iter::IntoIterator::into_iter($items), $key)
+    );
+}
+
+/// Concatenates the `Display` forms of its arguments into a `String`.
+///
+/// `string!(a, b, c)` gives the same string as `format!("{}{}{}", a, b, c)`,
+/// without needing a format string. The arguments are only borrowed, and each
+/// is evaluated and formatted exactly once, straight into the result. The
+/// lengths of the arguments which are string slices, `String`s or `char`s are
+/// added up first and reserved up front, so a string made only of those is
+/// allocated once, at exactly the size it needs.
+///
+/// # Example
+///
+/// ```
+/// let name = "world";
+/// let s = string!("hello, ", name, "! ", 42, '.');
+/// assert_eq!(s, "hello, world! 42.");
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! string {
+    () => ($crate::string::String::new());
+    ($($x:expr),+) => ({
+        #[allow(unused_imports)]
+        use $crate::rt::macro_support::{DisplayPiece, StrPiece};
+        $crate::rt::macro_support::concat_display(
+            &[$((&$crate::rt::macro_support::Piece(&$x)).piece()),+])
+    });
+    ($($x:expr),+,) => (string!($($x),+));
+}
+
+/// Creates an empty `String` with room for at least `n` bytes.
+///
+/// This is the same as `String::with_capacity(n)`.
+///
+/// # Example
+///
+/// ```
+/// let mut s = string_cap!(16);
+/// assert!(s.capacity() >= 16);
+/// s.push_str("no reallocation");
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! string_cap {
+    ($n:expr) => ($crate::string::String::with_capacity($n));
+}
+
+/// Runs each closure on its own scoped thread, and gives their values.
+///
+/// Like `join_threads!`, every closure is spawned with `Thread::scoped`
+/// before any are joined, and all of them have been joined by the time the
+/// macro returns, which is what allows them to borrow from the enclosing
+/// scope. The result is a tuple of the values the closures returned. If any
+/// of the threads panicked, the others are still joined first, and then the
+/// first panic (in argument order) is resumed in the calling thread, with
+/// its original payload. Up to twelve closures may be given.
+///
+/// # Example
+///
+/// ```
+/// let data = [1, 2, 3, 4, 5, 6];
+/// let (low, high) = scoped_tasks!([
+///     || data[..3].iter().fold(0, |a, &b| a + b),
+///     || data[3..].iter().fold(0, |a, &b| a + b),
+/// ]);
+/// assert_eq!(low + high, 21);
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! scoped_tasks {
+    ([$($f:expr),+]) => ({
+        static _FILE_LINE: (&'static str, usize) = (file!(), line!());
+        $crate::rt::macro_support::JoinAll::join_all_or_panic(
+            ($($crate::thread::Thread::scoped($f),)+),
+            &_FILE_LINE
+        )
+    });
+    ([$($f:expr),+,]) => (scoped_tasks!([$($f),+]));
+}
+
+/// Unwraps several `Option`s together.
+///
+/// `unwrap_all!(a, b, c)` evaluates to `Some((a, b, c))`, with the values
+/// inside each option, if every argument is `Some`, and to `None` otherwise.
+/// The arguments are evaluated in order, each exactly once, and as soon as
+/// one of them is `None` the rest are not evaluated at all.
+///
+/// # Example
+///
+/// ```
+/// let host = Some("localhost");
+/// let port = "8080".parse::<u16>().ok();
+/// assert_eq!(unwrap_all!(host, port), Some(("localhost", 8080)));
+///
+/// let missing: Option<u16> = None;
+/// assert_eq!(unwrap_all!(host, missing), None);
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! unwrap_all {
+    (@acc [$($v:ident)*]) => ($crate::option::Option::Some(($($v,)*)));
+    (@acc [$($v:ident)*] $x:expr $(, $rest:expr)*) => (
+        match $x {
+            $crate::option::Option::Some(v) => unwrap_all!(@acc [$($v)* v] $($rest),*),
+            $crate::option::Option::None => $crate::option::Option::None,
+        }
+    );
+    ($($x:expr),+) => (unwrap_all!(@acc [] $($x),+));
+    ($($x:expr),+,) => (unwrap_all!(@acc [] $($x),+));
+}
+
+/// Unwraps several `Result`s together.
+///
+/// This is the `Result` counterpart of `unwrap_all!`: `unwrap_all_ok!(a, b,
+/// c)` evaluates to `Ok((a, b, c))`, with the values inside each result, if
+/// every argument is `Ok`, and otherwise to the first `Err`. The arguments
+/// are evaluated in order, and none after the first `Err` are evaluated. All
+/// of the results must have the same error type.
+///
+/// # Example
+///
+/// ```
+/// let parsed = unwrap_all_ok!("1".parse::<u8>(), "2".parse::<u8>());
+/// assert_eq!(parsed, Ok((1, 2)));
+///
+/// assert!(unwrap_all_ok!("1".parse::<u8>(), "x".parse::<u8>()).is_err());
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! unwrap_all_ok {
+    (@acc [$($v:ident)*]) => ($crate::result::Result::Ok(($($v,)*)));
+    (@acc [$($v:ident)*] $x:expr $(, $rest:expr)*) => (
+        match $x {
+            $crate::result::Result::Ok(v) => unwrap_all_ok!(@acc [$($v)* v] $($rest),*),
+            $crate::result::Result::Err(e) => $crate::result::Result::Err(e),
+        }
+    );
+    ($($x:expr),+) => (unwrap_all_ok!(@acc [] $($x),+));
+    ($($x:expr),+,) => (unwrap_all_ok!(@acc [] $($x),+));
+}
+
+/// Prints a line to the task's stdout handle in colour.
+///
+/// `cprintln!(color, "format", args...)` formats its arguments as `println!`
+/// does, and wraps the line in the ANSI escapes which set the foreground
+/// colour and then reset it. The colour is one of `black`, `red`, `green`,
+/// `yellow`, `blue`, `magenta`, `cyan` and `white`.
+///
+/// The escapes are only written when stdout is a terminal, so output which is
+/// redirected to a file or pipe (or replaced with `set_stdout`) is left
+/// plain. Setting the `CLICOLOR_FORCE` environment variable to anything but
+/// `0` writes them regardless.
+///
+/// # Example
+///
+/// ```
+/// let failures = 3;
+/// cprintln!(red, "{} tests failed", failures);
+/// cprintln!(green, "done");
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! cprintln {
+    (@escape black) => ("\x1b[30m");
+    (@escape red) => ("\x1b[31m");
+    (@escape green) => ("\x1b[32m");
+    (@escape yellow) => ("\x1b[33m");
+    (@escape blue) => ("\x1b[34m");
+    (@escape magenta) => ("\x1b[35m");
+    (@escape cyan) => ("\x1b[36m");
+    (@escape white) => ("\x1b[37m");
+    ($color:ident, $($arg:tt)+) => (
+        $crate::rt::macro_support::cprintln(cprintln!(@escape $color), format_args!($($arg)+))
+    );
+}
+
+/// Combines several partial comparisons, in priority order, into one.
+///
+/// This is the `PartialOrd` counterpart of `chained_cmp!`: each argument is
+/// an `Option<Ordering>`, as returned by `partial_cmp`. They are evaluated
+/// left-to-right, and the result is `None` as soon as one is `None`, or else
+/// the first ordering which is not `Equal`, or `Some(Equal)` if they are all
+/// equal. Once the result is decided the remaining comparisons are not
+/// evaluated.
+///
+/// # Example
+///
+/// ```
+/// use std::cmp::Ordering;
+///
+/// struct Point { x: f64, y: f64 }
+///
+/// fn cmp_points(a: &Point, b: &Point) -> Option<Ordering> {
+///     partial_cmp_chain!(a.x.partial_cmp(&b.x), a.y.partial_cmp(&b.y))
+/// }
+///
+/// let a = Point { x: 1.0, y: 2.0 };
+/// let b = Point { x: 1.0, y: 3.5 };
+/// assert_eq!(cmp_points(&a, &b), Some(Ordering::Less));
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! partial_cmp_chain {
+    ($cmp:expr) => ($cmp);
+    ($cmp:expr, $($rest:expr),+) => (
+        match $cmp {
+            $crate::option::Option::Some($crate::cmp::Ordering::Equal) => {
+                partial_cmp_chain!($($rest),+)
+            }
+            ordering => ordering,
+        }
+    );
+    ($($cmp:expr),+,) => (partial_cmp_chain!($($cmp),+));
+}
+
+/// Reads a line of input from the task's stdin handle.
+///
+/// This evaluates to an `IoResult<String>` holding the next line, with its
+/// trailing newline (and any carriage return before it) removed. At the end
+/// of the input the result is an `EndOfFile` error. The line is read from the
+/// handle given to `set_stdin`, or from the process's stdin if none has been
+/// set.
+///
+/// With `read_line!(prompt: "format", args...)` the prompt is first printed
+/// to stdout, formatted as `print!` does, and stdout is flushed so that the
+/// prompt appears before the input is waited for.
+///
+/// # Example
+///
+/// ```no_run
+/// let name = read_line!(prompt: "Enter name: ").unwrap();
+/// println!("Hello, {}!", name);
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! read_line {
+    () => ($crate::old_io::stdio::read_task_line());
+    (prompt: $($arg:tt)+) => ({
+        $crate::old_io::stdio::print_args(format_args!($($arg)+));
+        $crate::old_io::stdio::flush();
+        $crate::old_io::stdio::read_task_line()
+    });
+}
+
+/// Reads an environment variable at runtime and parses it.
+///
+/// This is the runtime counterpart of `env_parse!`. `env_typed!(name, Type)`
+/// reads the environment variable `name` and parses it as `Type` with
+/// `FromStr`, giving a `Result<Type, env::ParseVarError<E>>`, where `E` is
+/// the type's parse error. A variable which can't be read gives
+/// `Err(ParseVarError::Var(..))`, and one which can't be parsed gives
+/// `Err(ParseVarError::Parse(..))`.
+///
+/// `env_typed!(name, Type, default)` instead evaluates to the parsed value,
+/// or to `default` in either of those cases. The default is only evaluated
+/// when it is needed.
+///
+/// # Example
+///
+/// ```
+/// let port = env_typed!("PORT", u16, 8080);
+/// println!("listening on port {}", port);
+///
+/// match env_typed!("WORKERS", usize) {
+///     Ok(n) => println!("using {} workers", n),
+///     Err(e) => println!("WORKERS: {}", e),
+/// }
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! env_typed {
+    ($name:expr, $t:ty) => ($crate::rt::macro_support::env_typed::<$t>($name));
+    ($name:expr, $t:ty, $default:expr) => (
+        match $crate::rt::macro_support::env_typed::<$t>($name) {
+            $crate::result::Result::Ok(v) => v,
+            $crate::result::Result::Err(..) => $default,
+        }
+    );
+}
+
+/// Times two closures against each other.
+///
+/// `bench_compare!(n, "a" => || f(), "b" => || g())` times each closure over
+/// `n` iterations, as `benchmark!` does, printing a line for each to the
+/// task's stderr handle. It then prints `a vs b: x speedup`, where `x` is
+/// the time per iteration of the second closure divided by that of the first,
+/// so that a value above one means the first closure is faster. It evaluates
+/// to a tuple of the two mean times per iteration, in nanoseconds.
+///
+/// # Example
+///
+/// ```
+/// let v = (0..1000u32).collect::<Vec<_>>();
+/// let (fold, manual) = bench_compare!(100,
+///     "fold" => || v.iter().fold(0, |a, &b| a + b),
+///     "loop" => || {
+///         let mut sum = 0;
+///         for &x in v.iter() { sum += x; }
+///         sum
+///     });
+/// assert!(fold > 0.0 && manual > 0.0);
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! bench_compare {
+    ($iters:expr, $label_a:expr => $fa:expr, $label_b:expr => $fb:expr) => (
+        $crate::rt::macro_support::bench_compare($iters as u64, $label_a, $fa, $label_b, $fb)
+    );
+    ($iters:expr, $label_a:expr => $fa:expr, $label_b:expr => $fb:expr,) => (
+        bench_compare!($iters, $label_a => $fa, $label_b => $fb)
+    );
+}
+
+/// Creates a `Vec` containing the arguments, of which there must be at least
+/// one.
+///
+/// This is the same as `vec!` with a list of elements, and allocates the
+/// vector at its final size in the same way, but invoking it with no
+/// arguments is a compile error rather than an empty vector. It is meant for
+/// APIs which need at least one element.
+///
+/// # Example
+///
+/// ```
+/// let hosts = nonempty_vec!["primary", "backup"];
+/// assert_eq!(hosts[0], "primary");
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! nonempty_vec {
+    () => (compile_error!("nonempty_vec! needs at least one element"));
+    ($($x:expr),+) => (vec![$($x),+]);
+    ($($x:expr),+,) => (vec![$($x),+]);
+}
+
+/// Prints key/value pairs to the task's stderr handle as a JSON-like object.
+///
+/// `println_json!{ "name": name, "count": count }` prints a line such as
+/// `{"name": "widget", "count": 3}`. The keys must be string literals, and the
+/// values may be of any type implementing `Debug`, which is used to format
+/// them, so that strings are quoted and numbers are not. The values are only
+/// borrowed.
+///
+/// This is a debugging aid rather than a serializer: the output is only JSON
+/// when every value's `Debug` form happens to be. Structs, enums, and
+/// collections are written in their usual `Debug` syntax, which JSON parsers
+/// won't accept.
+///
+/// # Example
+///
+/// ```
+/// let name = "widget";
+/// let count = 3;
+/// println_json!{ "name": name, "count": count };
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! println_json {
+    ($($key:tt: $value:expr),*) => (
+        $crate::rt::macro_support::println_json(&[$(($key, &$value as &$crate::fmt::Debug)),*])
+    );
+    ($($key:tt: $value:expr),+,) => (println_json!($($key: $value),+));
+}
+
+/// Evaluates integer arithmetic with wrapping on overflow.
+///
+/// `wrapping!(a + b * c)` rewrites the `+`, `-` and `*` operators in the
+/// expression into calls to `Int::wrapping_add`, `wrapping_sub` and
+/// `wrapping_mul`, so every operation wraps around at the bounds of the type
+/// with two's complement semantics instead of overflowing. `*` binds more
+/// tightly than `+` and `-`, and operators of the same precedence associate
+/// to the left, as usual. Each operand is evaluated once, from left to right.
+///
+/// Only those three binary operators are supported. Each operand must be an
+/// identifier, a path of up to three segments such as `i32::MAX`, a literal,
+/// a parenthesised expression (which is rewritten in turn), or a block.
+/// Anything else, such as a method call or a unary `-`, can be written inside
+/// a block: `wrapping!({ v.len() } * 2)`.
+///
+/// # Example
+///
+/// ```
+/// let x = 200u8;
+/// assert_eq!(wrapping!(x + 100), 44);
+/// assert_eq!(wrapping!(x * 2 - (x + 56)), 144);
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! wrapping {
+    (@atom ($($e:tt)+)) => (wrapping!($($e)+));
+    (@atom $a:tt) => ($a);
+
+    // Building up a product, to be added to or subtracted from `$sum`.
+    (@prod [$($sum:tt)*] $p:expr; * $b:tt $($rest:tt)*) => (
+        wrapping!(@prod [$($sum)*] $crate::num::Int::wrapping_mul($p, wrapping!(@atom $b));
+                  $($rest)*)
+    );
+    (@prod [] $p:expr; $($rest:tt)*) => (wrapping!(@sum $p; $($rest)*));
+    (@prod [$sum:expr, +] $p:expr; $($rest:tt)*) => (
+        wrapping!(@sum $crate::num::Int::wrapping_add($sum, $p); $($rest)*)
+    );
+    (@prod [$sum:expr, -] $p:expr; $($rest:tt)*) => (
+        wrapping!(@sum $crate::num::Int::wrapping_sub($sum, $p); $($rest)*)
+    );
+
+    // A finished sum, which may be followed by another term.
+    (@sum $sum:expr;) => ($sum);
+    (@sum $sum:expr; + $a:tt $($rest:tt)*) => (
+        wrapping!(@prod [$sum, +] wrapping!(@atom $a); $($rest)*)
+    );
+    (@sum $sum:expr; - $a:tt $($rest:tt)*) => (
+        wrapping!(@prod [$sum, -] wrapping!(@atom $a); $($rest)*)
+    );
+    (@sum $sum:expr; $($rest:tt)+) => (
+        compile_error!("wrapping! only supports `+`, `-` and `*` between operands")
+    );
+
+    // Paths are made into blocks, so that every operand is a single token
+    // tree by the time the expression is rewritten.
+    (@paths [$($done:tt)*] $a:ident :: $b:ident :: $c:ident $($rest:tt)*) => (
+        wrapping!(@paths [$($done)* { $a::$b::$c }] $($rest)*)
+    );
+    (@paths [$($done:tt)*] $a:ident :: $b:ident $($rest:tt)*) => (
+        wrapping!(@paths [$($done)* { $a::$b }] $($rest)*)
+    );
+    (@paths [$($done:tt)*] $t:tt $($rest:tt)*) => (wrapping!(@paths [$($done)* $t] $($rest)*));
+    (@paths [$a:tt $($rest:tt)*]) => (wrapping!(@prod [] wrapping!(@atom $a); $($rest)*));
+
+    ($($t:tt)+) => (wrapping!(@paths [] $($t)+));
+}
+
+/// Flattens an iterable of iterables into a `Vec`.
+///
+/// `flatten_collect!(items)` takes anything implementing `IntoIterator` whose
+/// items implement `IntoIterator` themselves, and collects the items of each
+/// inner iterable, in order, into a single `Vec`. The vector is allocated up
+/// front from the sum of the inner iterators' size hints, so that it doesn't
+/// have to grow while it is being filled when those hints are exact.
+///
+/// # Example
+///
+/// ```
+/// let v = flatten_collect!(vec![vec![1, 2], vec![], vec![3]]);
+/// assert_eq!(v, vec![1, 2, 3]);
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! flatten_collect {
+    ($items:expr) => (
+        $crate::rt::macro_support::flatten_collect($crate::iter::IntoIterator::into_iter($items))
+    );
+}
+
+/// Times the rest of the enclosing scope, in debug builds only.
+///
+/// `debug_timer!("label");` is a statement. It notes the time, and when the
+/// scope it appears in ends it prints `label: <elapsed>` to the task's stderr
+/// handle, with the elapsed time formatted as by `fmt_duration!`. The guard
+/// which does this is dropped along with the scope's other locals, so the
+/// time is also printed if the scope is left early, by a `return` or a panic.
+///
+/// When compiling with `--cfg ndebug` the guard neither reads the clock nor
+/// prints anything.
+///
+/// # Example
+///
+/// ```
+/// fn load() -> Vec<u8> {
+///     debug_timer!("load");
+///     vec![0; 1024]
+/// }
+/// // prints something like "load: 3us"
+/// load();
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! debug_timer {
+    ($label:expr) => (
+        let _timer = $crate::rt::macro_support::DebugTimer::new($label, cfg!(not(ndebug)));
+    );
+}
+
+/// Collects an iterable into a sorted `Vec`.
+///
+/// `sorted_vec!(items)` collects anything implementing `IntoIterator` into a
+/// `Vec` and sorts it in ascending order, which requires the elements to
+/// implement `Ord`. The sort is stable, so equal elements keep the order they
+/// came in.
+///
+/// See also `sorted_vec_by!` and `sorted_vec_desc!`.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(sorted_vec!(vec![3, 1, 2]), vec![1, 2, 3]);
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! sorted_vec {
+    ($items:expr) => ({
+        let mut v = $crate::iter::IntoIterator::into_iter($items)
+                        .collect::<$crate::vec::Vec<_>>();
+        v.sort();
+        v
+    });
+}
+
+/// Collects an iterable into a `Vec` sorted with a comparator.
+///
+/// `sorted_vec_by!(items, |a, b| ...)` is like `sorted_vec!`, but orders the
+/// elements with the given closure, which takes two references to elements
+/// and returns an `Ordering`, as for `sort_by`.
+///
+/// # Example
+///
+/// ```
+/// let v = sorted_vec_by!(vec!["ccc", "a", "bb"], |a, b| a.len().cmp(&b.len()));
+/// assert_eq!(v, vec!["a", "bb", "ccc"]);
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! sorted_vec_by {
+    ($items:expr, $cmp:expr) => ({
+        let mut v = $crate::iter::IntoIterator::into_iter($items)
+                        .collect::<$crate::vec::Vec<_>>();
+        v.sort_by($cmp);
+        v
+    });
+}
+
+/// Collects an iterable into a `Vec` sorted in descending order.
+///
+/// `sorted_vec_desc!(items)` is like `sorted_vec!`, but puts the largest
+/// element first. Equal elements still keep the order they came in.
+///
+/// # Example
+///
+/// ```
+/// assert_eq!(sorted_vec_desc!(vec![3, 1, 2]), vec![3, 2, 1]);
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! sorted_vec_desc {
+    ($items:expr) => (sorted_vec_by!($items, |a, b| b.cmp(a)));
+}
+
+/// Chooses between two expressions, like C's `?:` operator.
+///
+/// `ternary!(cond, a, b)` is exactly `if cond { a } else { b }`: only the
+/// chosen expression is evaluated, and the two must have the same type.
+///
+/// # Example
+///
+/// ```
+/// let n = 3;
+/// assert_eq!(ternary!(n % 2 == 0, "even", "odd"), "odd");
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! ternary {
+    ($cond:expr, $a:expr, $b:expr) => (if $cond { $a } else { $b });
+}
+
+/// Redraws a progress bar on the current line of the terminal.
+///
+/// `print_progress!(current, total)` prints a carriage return followed by a
+/// bar and a percentage, as in `[##########          ]  50% (5/10)`, to the
+/// task's stdout handle without a newline, and flushes it. Calling it again
+/// overwrites the line in place; once the work is finished,
+/// `print_progress_done!()` moves on to a new line. Both counts may be of any
+/// integer type, and `current` is capped at `total`.
+///
+/// Nothing at all is printed when stdout isn't a terminal, so that progress
+/// updates don't fill up logs. As with `cprintln!`, setting the
+/// `CLICOLOR_FORCE` environment variable to anything but `0` prints the bar
+/// regardless.
+///
+/// # Example
+///
+/// ```
+/// let items = vec![1, 2, 3, 4];
+/// for (i, _) in items.iter().enumerate() {
+///     print_progress!(i + 1, items.len());
+/// }
+/// print_progress_done!();
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! print_progress {
+    ($current:expr, $total:expr) => (
+        $crate::rt::macro_support::print_progress($current as u64, $total as u64)
+    );
+}
+
+/// Ends a line of progress drawn by `print_progress!`.
+///
+/// `print_progress_done!()` prints a newline, so that later output doesn't
+/// overwrite the final progress bar. Like `print_progress!`, it prints
+/// nothing if stdout isn't a terminal.
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! print_progress_done {
+    () => ($crate::rt::macro_support::print_progress_done());
+}
+
+/// Builds a `PathBuf` by joining components in order.
+///
+/// `path!(a, b, c)` is the same as `Path::new(&a).join(&b).join(&c)`: the
+/// first component is the base, and each later one is adjoined to the path
+/// so far with `PathBuf::push`. The components may be `&str`s, `String`s,
+/// `Path`s, or anything else implementing `AsOsStr`, and are only borrowed.
+///
+/// As with `push`, a later component which is absolute replaces the path
+/// built so far.
+///
+/// # Example
+///
+/// ```
+/// use std::path::Path;
+///
+/// let dir = String::from_str("src");
+/// let p = path!("/home", dir, "main.rs");
+/// assert_eq!(p, Path::new("/home").join("src").join("main.rs"));
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! path {
+    ($first:expr $(, $rest:expr)*) => ({
+        let mut p = $crate::path::PathBuf::new(&$first);
+        $(p.push(&$rest);)*
+        p
+    });
+    ($first:expr $(, $rest:expr)*,) => (path!($first $(, $rest)*));
+}
+
+/// Retries an I/O operation for as long as it is interrupted.
+///
+/// `retry_io!(expr)` evaluates `expr`, which must give either an
+/// `io::Result` or an `old_io::IoResult`, and evaluates it again each time it
+/// fails because it was interrupted (`EINTR` on Unix): with an error of kind
+/// `io::ErrorKind::Interrupted` or `old_io::Interrupted` respectively. The
+/// first result which isn't an interruption, successful or not, is the value
+/// of the macro.
+///
+/// Unlike `retry_until!`, there is no limit on the number of attempts and no
+/// pause between them, since an interrupted call can simply be restarted.
+///
+/// # Example
+///
+/// ```
+/// use std::io::Read;
+///
+/// let mut buf = [0; 4];
+/// let mut input: &[u8] = b"data";
+/// let n = retry_io!(input.read(&mut buf)).unwrap();
+/// assert_eq!(n, 4);
+///
+/// let mut reader = std::old_io::MemReader::new(b"old data".to_vec());
+/// let line = retry_io!(reader.read_to_string()).unwrap();
+/// assert_eq!(line, "old data");
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! retry_io {
+    ($e:expr) => ({
+        let mut result;
+        loop {
+            result = $e;
+            match result {
+                $crate::result::Result::Err(ref e)
+                    if $crate::rt::macro_support::IoInterrupted::is_interrupted(e) => {}
+                _ => break,
+            }
+        }
+        result
+    });
+}
+
+/// Implements `Debug` for a struct, showing only some of its fields.
+///
+/// `debug_fields!(Point { x, y => "why" });` implements `fmt::Debug` for the
+/// struct `Point`, writing it in the same form as `#[derive(Debug)]` but
+/// listing only the given fields, in the given order. A field may be followed
+/// by `=> "label"` to show it under a different name. Fields which aren't
+/// listed are left out entirely, which is useful for large caches or for
+/// values which shouldn't appear in logs. Each listed field must implement
+/// `Debug` itself.
+///
+/// # Example
+///
+/// ```
+/// struct Login { user: String, password: String, attempts: u32 }
+/// debug_fields!(Login { user, attempts => "tries" });
+///
+/// let l = Login { user: "root".to_string(), password: "hunter2".to_string(), attempts: 3 };
+/// assert_eq!(format!("{:?}", l), "Login { user: \"root\", tries: 3 }");
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! debug_fields {
+    (@label $field:ident) => (stringify!($field));
+    (@label $field:ident $label:expr) => ($label);
+    ($name:ident {}) => (
+        impl $crate::fmt::Debug for $name {
+            fn fmt(&self, f: &mut $crate::fmt::Formatter) -> $crate::fmt::Result {
+                f.write_str(stringify!($name))
+            }
+        }
+    );
+    ($name:ident { $first:ident $(=> $first_label:expr)*
+                   $(, $field:ident $(=> $label:expr)*)* }) => (
+        impl $crate::fmt::Debug for $name {
+            fn fmt(&self, f: &mut $crate::fmt::Formatter) -> $crate::fmt::Result {
+                try!(write!(f, "{} {{ {}: {:?}", stringify!($name),
+                            debug_fields!(@label $first $($first_label)*), self.$first));
+                $(
+                    try!(write!(f, ", {}: {:?}",
+                                debug_fields!(@label $field $($label)*), self.$field));
+                )*
+                f.write_str(" }")
+            }
+        }
+    );
+    ($name:ident { $($field:ident $(=> $label:expr)*),+, }) => (
+        debug_fields!($name { $($field $(=> $label)*),+ });
+    );
+}
+
+/// Indexes a slice, giving `None` when the index is out of bounds.
+///
+/// `checked_index!(s, i)` gives `Some(&s[i])`, or `None` instead of
+/// panicking when `i` isn't less than the length of `s`. Ranges work too:
+/// `checked_index!(s, a..b)` gives `Some(&s[a..b])` if `a <= b <= s.len()`,
+/// and `None` otherwise, and likewise for `a..`, `..b` and `..`. The slice
+/// may be anything which can be sliced with `[]`, such as a `Vec` or an
+/// array.
+///
+/// # Example
+///
+/// ```
+/// let v = vec![10, 20, 30];
+/// assert_eq!(checked_index!(v, 1), Some(&20));
+/// assert_eq!(checked_index!(v, 3), None);
+/// assert_eq!(checked_index!(v, 1..3), Some(&[20, 30][]));
+/// assert_eq!(checked_index!(v, 2..4), None);
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! checked_index {
+    ($s:expr, $idx:expr) => (
+        $crate::rt::macro_support::CheckedIndex::checked_index(&$s[], $idx)
+    );
+}
+
+/// Swaps the values of two fields of the same struct.
+///
+/// `swap_fields!(obj, a, b)` exchanges `obj.a` and `obj.b`. The two fields
+/// must have the same type, and must be different fields. `obj` is
+/// evaluated once, and borrowed mutably, so it must be a mutable place such
+/// as a `mut` local variable, a field, or `*r` for a `&mut` reference `r`
+/// (inside a `&mut self` method, that is `swap_fields!(*self, a, b)`).
+///
+/// Writing `mem::swap(&mut obj.a, &mut obj.b)` by hand needs `obj` to be
+/// written twice; this borrows it once, and then borrows the two fields from
+/// that, which the borrow checker knows don't overlap.
+///
+/// # Example
+///
+/// ```
+/// struct Buffers { front: Vec<u8>, back: Vec<u8> }
+///
+/// let mut b = Buffers { front: vec![1], back: vec![2, 3] };
+/// swap_fields!(b, front, back);
+/// assert_eq!(b.front, vec![2, 3]);
+/// assert_eq!(b.back, vec![1]);
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! swap_fields {
+    ($obj:expr, $a:ident, $b:ident) => ({
+        let obj = &mut $obj;
+        $crate::mem::swap(&mut obj.$a, &mut obj.$b)
+    });
+}
+
+/// Asserts that a collection has the expected length.
+///
+/// `assert_len!(coll, n)` panics unless `coll.len() == n`, with a message
+/// giving both lengths. `coll` may be anything with a `len` method and a
+/// `Debug` implementation, such as a slice, `Vec`, `String` or map, and is
+/// only borrowed. If it has no more than sixteen elements, its contents are
+/// included in the message too.
+///
+/// A custom message can follow the length, in the same form as the arguments
+/// to `format!`, and is added to the end of the panic message.
+///
+/// # Example
+///
+/// ```
+/// let v = vec![1, 2, 3];
+/// assert_len!(v, 3);
+/// assert_len!("hello", 5, "greeting was cut short");
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! assert_len {
+    ($coll:expr, $n:expr) => ({
+        match (&$coll, $n) {
+            (coll, n) => {
+                let len = coll.len();
+                if len != n {
+                    panic!("assertion failed: `{}` has length {}, expected {}{}",
+                           stringify!($coll), len, n,
+                           $crate::rt::macro_support::len_contents(coll, len))
+                }
+            }
+        }
+    });
+    ($coll:expr, $n:expr, $($arg:tt)+) => ({
+        match (&$coll, $n) {
+            (coll, n) => {
+                let len = coll.len();
+                if len != n {
+                    panic!("assertion failed: `{}` has length {}, expected {}{}: {}",
+                           stringify!($coll), len, n,
+                           $crate::rt::macro_support::len_contents(coll, len),
+                           format_args!($($arg)+))
+                }
+            }
+        }
+    });
+}
+
+/// Builds a `String` by writing to it.
+///
+/// `build_string!(|s| { ... })` makes a new, empty `String`, runs the block
+/// with `s` bound to a mutable reference to it, and evaluates to the string.
+/// `fmt::Writer` is in scope inside the block, so `write!` and `writeln!`
+/// can be used on `s` directly, and the block may use `try!` on their
+/// results, since writing to a `String` never fails by itself.
+///
+/// # Example
+///
+/// ```
+/// let items = ["a", "b", "c"];
+/// let s = build_string!(|s| {
+///     for (i, item) in items.iter().enumerate() {
+///         try!(write!(s, "{}={} ", item, i));
+///     }
+///     s.push_str("end");
+/// });
+/// assert_eq!(s, "a=0 b=1 c=2 end");
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! build_string {
+    (|$s:ident| $body:expr) => (
+        $crate::rt::macro_support::build_string(
+            |$s: &mut $crate::string::String| -> $crate::fmt::Result {
+                #[allow(unused_imports)]
+                use $crate::fmt::Writer;
+                let _ = $body;
+                $crate::result::Result::Ok(())
+            })
+    );
+}
+
+/// Counts the leading elements of an iterable which satisfy a predicate.
+///
+/// `count_while!(items, |x| pred)` takes anything implementing
+/// `IntoIterator`, and gives the number of elements before the first one for
+/// which the closure returns `false`, as a `usize`. The closure is called
+/// with a reference to each element, as for `take_while`. Nothing after the
+/// first failing element is taken from the iterator.
+///
+/// # Example
+///
+/// ```
+/// let line = "   indented";
+/// assert_eq!(count_while!(line.chars(), |c| *c == ' '), 3);
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! count_while {
+    ($items:expr, $pred:expr) => (
+        $crate::iter::IntoIterator::into_iter($items).take_while($pred).count()
+    );
+}
+
+/// Reads a list of items from an environment variable at runtime.
+///
+/// `env_list!("HOSTS", ',')` reads the variable and splits its value at each
+/// occurrence of the delimiter, which may be anything `str::split` accepts,
+/// such as a `char`. Each item has surrounding whitespace trimmed, and empty
+/// items are dropped, so `"a, b,,c "` gives `["a", "b", "c"]`. The result is
+/// a `Vec<String>`, which is empty if the variable isn't set (or isn't valid
+/// unicode).
+///
+/// `env_list!("PORTS", ',', u16)` parses each item into the given type with
+/// `FromStr`, and gives a `Result<Vec<u16>, _>` holding the error from the
+/// first item which fails to parse.
+///
+/// # Example
+///
+/// ```
+/// use std::env;
+///
+/// env::set_var("PORTS", "80, 443");
+/// assert_eq!(env_list!("PORTS", ','), vec!["80", "443"]);
+/// assert_eq!(env_list!("PORTS", ',', u16), Ok(vec![80, 443]));
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! env_list {
+    ($key:expr, $delim:expr) => ($crate::rt::macro_support::env_list($key, $delim));
+    ($key:expr, $delim:expr, $t:ty) => (
+        $crate::rt::macro_support::env_list_typed::<$t, _>($key, $delim)
+    );
+}
+
+/// Loops over an iterable with a counter which the body can change.
+///
+/// `loop_with_index!(i in items => { ... })` runs the body once for each
+/// element of `items`, which may be anything implementing `IntoIterator`,
+/// with `i` bound to a mutable `usize` counting the iterations from zero.
+/// `loop_with_index!((i, x) in items => { ... })` also binds each element to
+/// the pattern `x`.
+///
+/// Unlike with `enumerate`, the counter is an ordinary variable: the body may
+/// assign to it, and the next iteration carries on counting from the new
+/// value. `break` and `continue` work as in a `for` loop, and the counter is
+/// still advanced after a `continue`.
+///
+/// # Example
+///
+/// ```
+/// let mut seen = vec![];
+/// loop_with_index!((i, c) in "abcd".chars() => {
+///     if c == 'b' {
+///         // count in tens from here on
+///         i *= 10;
+///     }
+///     seen.push((i, c));
+/// });
+/// assert_eq!(seen, vec![(0, 'a'), (10, 'b'), (11, 'c'), (12, 'd')]);
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! loop_with_index {
+    (($i:ident, $x:pat) in $items:expr => $body:block) => ({
+        let mut $i: usize = 0;
+        let mut started = false;
+        for $x in $crate::iter::IntoIterator::into_iter($items) {
+            if started { $i += 1; } else { started = true; }
+            $body
+        }
+    });
+    ($i:ident in $items:expr => $body:block) => (
+        loop_with_index!(($i, _) in $items => $body)
+    );
+}
+
+/// Formats a value padded to a width chosen at runtime.
+///
+/// `format_aligned!(value, width, left)` formats `value` with `Display` and
+/// pads it with spaces to at least `width` characters, giving a `String`.
+/// When `left` is true the value is left-aligned, with the spaces after it,
+/// and otherwise it is right-aligned. Widths are counted in characters, not
+/// bytes, and a value which is already wider is left as it is rather than
+/// truncated.
+///
+/// This is the same as `format!("{:<1$}", value, width)` (or `{:>1$}`), except
+/// that the padding is applied even to values whose `Display` implementation
+/// doesn't support it.
+///
+/// # Example
+///
+/// ```
+/// let width = 6;
+/// assert_eq!(format_aligned!("ab", width, true), "ab    ");
+/// assert_eq!(format_aligned!(42, width, false), "    42");
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! format_aligned {
+    ($value:expr, $width:expr, $left:expr) => (
+        $crate::rt::macro_support::format_aligned(&$value, $width, $left)
+    );
+}
+
+/// Traces entry to and exit from a block, indented by nesting, in debug
+/// builds only.
+///
+/// `debug_scope!("label", { ... })` prints `> label` to the task's stderr
+/// handle, runs the block, and then prints `< label`, evaluating to the
+/// block's value. The lines are indented by two spaces for each
+/// `debug_scope!` the block is nested inside, counted per task, so nested
+/// scopes show up as a tree. The exit line is printed however the block is
+/// left, including by `return`, `break` or a panic.
+///
+/// When compiling with `--cfg ndebug` the block is run as it is, and nothing
+/// is printed.
+///
+/// # Example
+///
+/// ```
+/// let n = debug_scope!("outer", {
+///     debug_scope!("inner", { 1 + 1 })
+/// });
+/// assert_eq!(n, 2);
+/// // prints:
+/// // > outer
+/// //   > inner
+/// //   < inner
+/// // < outer
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! debug_scope {
+    ($label:expr, $body:block) => ({
+        let _scope = $crate::rt::macro_support::DebugScope::enter($label, cfg!(not(ndebug)));
+        $body
+    });
+}
+
+/// Locks a mutex if it can be done without blocking.
+///
+/// `try_lock!(mutex)` calls `mutex.try_lock()`, and gives `Some(guard)` if
+/// the lock was acquired, or `None` if it is currently held elsewhere.
+/// `try_lock!(mutex, else expr)` gives the guard itself, and evaluates `expr`
+/// instead when the lock is held; `expr` must diverge, as with `return`,
+/// `continue` or `panic!`.
+///
+/// As with `mutex.lock().unwrap()`, this panics if the mutex has been
+/// poisoned by a task which panicked while holding it.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::Mutex;
+///
+/// fn bump(m: &Mutex<u32>) -> bool {
+///     let mut n = try_lock!(m, else return false);
+///     *n += 1;
+///     true
+/// }
+///
+/// let m = Mutex::new(0);
+/// assert!(bump(&m));
+/// {
+///     let _held = try_lock!(m).unwrap();
+///     assert!(try_lock!(m).is_none());
+///     assert!(!bump(&m));
+/// }
+/// assert_eq!(*m.lock().unwrap(), 1);
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! try_lock {
+    ($mutex:expr) => (
+        match $mutex.try_lock() {
+            $crate::result::Result::Ok(guard) => $crate::option::Option::Some(guard),
+            $crate::result::Result::Err($crate::sync::TryLockError::WouldBlock) => {
+                $crate::option::Option::None
+            }
+            $crate::result::Result::Err(e) => panic!("{}", e),
+        }
+    );
+    ($mutex:expr, else $otherwise:expr) => (
+        match try_lock!($mutex) {
+            $crate::option::Option::Some(guard) => guard,
+            $crate::option::Option::None => $otherwise,
+        }
+    );
+}
+
+/// Selects an event from a number of receivers, preferring earlier arms.
+///
+/// `select_biased!` takes the same arms as `select!`, except that each must
+/// call `recv`. The receivers are checked in the order the arms are written,
+/// and the first one with a value waiting (or whose senders have all gone)
+/// has its arm run, with the pattern bound to the result of `recv`. If none
+/// of them is ready, this blocks until one is, and then checks them all in
+/// order again, so when several receivers become ready together the one
+/// listed first is always chosen. This makes the choice reproducible, which
+/// `select!` doesn't promise.
+///
+/// The price of that is fairness: as long as an earlier receiver always has
+/// a value waiting, later arms never run. Only list a busy receiver first if
+/// the others can afford to wait for it to drain.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::mpsc::channel;
+///
+/// let (urgent_tx, urgent) = channel();
+/// let (normal_tx, normal) = channel();
+/// normal_tx.send("normal").unwrap();
+/// urgent_tx.send("urgent").unwrap();
+///
+/// let first = select_biased! {
+///     msg = urgent.recv() => msg.unwrap(),
+///     msg = normal.recv() => msg.unwrap()
+/// };
+/// assert_eq!(first, "urgent");
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! select_biased {
+    (
+        $($name:pat = $rx:ident.recv() => $code:expr),+
+    ) => ({
+        use $crate::sync::mpsc::{RecvError, Select, TryRecvError};
+        let sel = Select::new();
+        // Each receiver is shadowed by its handle, along with a slot for the
+        // value taken from it.
+        $( let mut $rx = (sel.handle(&$rx), $crate::option::Option::None); )+
+        unsafe {
+            $( $rx.0.add(); )+
+        }
+        let mut ready = $crate::option::Option::None;
+        loop {
+            $(
+                if ready.is_none() {
+                    $rx.1 = match $rx.0.try_recv() {
+                        $crate::result::Result::Ok(v) => {
+                            $crate::option::Option::Some($crate::result::Result::Ok(v))
+                        }
+                        $crate::result::Result::Err(TryRecvError::Disconnected) => {
+                            $crate::option::Option::Some($crate::result::Result::Err(RecvError))
+                        }
+                        $crate::result::Result::Err(TryRecvError::Empty) => {
+                            $crate::option::Option::None
+                        }
+                    };
+                    if $rx.1.is_some() { ready = $crate::option::Option::Some($rx.0.id()); }
+                }
+            )+
+            if ready.is_some() { break }
+            sel.wait();
+        }
+        $(
+            if ready == $crate::option::Option::Some($rx.0.id()) {
+                let $name = $rx.1.take().unwrap();
+                $code
+            } else
+        )+
+        { unreachable!() }
+    })
+}
+
+/// Converts each of its arguments to a `String`, giving a tuple.
+///
+/// `to_string_all!(a, b, c)` is `(a.to_string(), b.to_string(),
+/// c.to_string())`: each argument must implement `Display`, is evaluated
+/// once, in order, and is only borrowed. A single argument gives a
+/// one-element tuple.
+///
+/// # Example
+///
+/// ```
+/// let (n, s) = to_string_all!(42, "x");
+/// assert_eq!(n, "42");
+/// assert_eq!(s, "x");
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! to_string_all {
+    ($($x:expr),+) => (($($crate::string::ToString::to_string(&$x),)+));
+    ($($x:expr),+,) => (to_string_all!($($x),+));
+}
+
+/// Runs a block, catching a panic as its message.
+///
+/// `capture_panic_message!({ ... })` runs the block and gives `Ok` of its
+/// value, or, if it panics, `Err` of the panic message as a `String`. Panics
+/// started with `panic!`, a `String` or a `&str` (and with `panic_bt!`) have
+/// their message recovered; for any other payload, the message is
+/// `Box<Any>`, as it is in the line printed when a thread panics.
+///
+/// The block is run on the current thread, so it may use anything in the
+/// enclosing scope, `Send` or not, and sees the thread's own `set_stdout` and
+/// `set_stderr` handles. The usual message is still printed to stderr when the
+/// block panics. Any values the block was modifying when it panicked are left
+/// as they were at that point.
+///
+/// # Example
+///
+/// ```
+/// let v = vec![1, 2, 3];
+/// assert_eq!(capture_panic_message!({ v[1] }), Ok(2));
+///
+/// let r = capture_panic_message!({ if v.len() > 2 { panic!("too long: {}", v.len()) } });
+/// assert_eq!(r, Err("too long: 3".to_string()));
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! capture_panic_message {
+    ($body:block) => ($crate::rt::macro_support::capture_panic_message(|| $body));
+}
+
+/// Prints a slice in fixed-size chunks to the task's stderr handle.
+///
+/// `print_chunks!(s, n)` prints the elements of `s` in groups of `n`, one
+/// group per line, formatted with `Debug` and prefixed by the index of the
+/// group's first element. The last group holds whatever is left over, so it
+/// may be shorter. The argument may be anything which can be sliced with
+/// `[]`, and is only borrowed.
+///
+/// # Panics
+///
+/// Panics if `n` is zero.
+///
+/// # Example
+///
+/// ```
+/// let v = (0..10).collect::<Vec<u32>>();
+/// print_chunks!(v, 4);
+/// // 0: [0, 1, 2, 3]
+/// // 4: [4, 5, 6, 7]
+/// // 8: [8, 9]
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! print_chunks {
+    ($s:expr, $size:expr) => ($crate::rt::macro_support::print_chunks(&$s[], $size));
+}
+
+/// Runs a closure with a mutex locked.
+///
+/// `with_lock!(mutex, |guard| { ... })` locks the mutex, blocking until it is
+/// available, and calls the closure with a mutable reference to the guard,
+/// through which the protected data can be used. The lock is released as soon
+/// as the closure returns, and the macro evaluates to the closure's value.
+/// Since the closure only borrows the guard, it can't be kept past the end of
+/// the critical section by mistake.
+///
+/// As with `mutex.lock().unwrap()`, this panics if the mutex has been
+/// poisoned by a task which panicked while holding it.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::Mutex;
+///
+/// let log = Mutex::new(vec![]);
+/// let len = with_lock!(log, |entries| {
+///     entries.push("started");
+///     entries.len()
+/// });
+/// assert_eq!(len, 1);
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! with_lock {
+    ($mutex:expr, $f:expr) => (
+        match $mutex.lock() {
+            $crate::result::Result::Ok(guard) => {
+                $crate::rt::macro_support::with_guard(guard, $f)
+            }
+            $crate::result::Result::Err(e) => panic!("{}", e),
+        }
+    );
+}
+
+/// Formats a list of items as a numbered list.
+///
+/// `numbered_list!(items)` takes anything implementing `IntoIterator` whose
+/// items implement `Display`, and gives a `String` with each item on its own
+/// line, prefixed by its number and a dot: `1. first`, `2. second`, and so
+/// on. `numbered_list!(items, from: n)` starts counting at `n` instead of 1.
+/// There is no newline after the last item, and an empty list gives an empty
+/// string.
+///
+/// # Example
+///
+/// ```
+/// let steps = ["fetch", "build", "test"];
+/// assert_eq!(numbered_list!(steps.iter()), "1. fetch\n2. build\n3. test");
+/// assert_eq!(numbered_list!(steps.iter().take(2), from: 0), "0. fetch\n1. build");
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! numbered_list {
+    ($items:expr) => (numbered_list!($items, from: 1));
+    ($items:expr, from: $from:expr) => (
+        $crate::rt::macro_support::numbered_list($crate::iter::IntoIterator::into_iter($items),
+                                                 $from)
+    );
+}
+
+/// Asserts that a string contains a substring, or a collection an element.
+///
+/// `assert_contains!(haystack, needle)` panics unless `needle` occurs in
+/// `haystack`. The haystack may be anything which can be sliced with `[]`:
+/// a `String` or `&str`, searched for a `&str`, `String` or `char`, or a
+/// `Vec`, array or slice, searched for an element. Both are only borrowed,
+/// and both are shown with `Debug` in the panic message.
+///
+/// A custom message can follow the needle, in the same form as the arguments
+/// to `format!`, and is added to the end of the panic message.
+///
+/// # Example
+///
+/// ```
+/// let greeting = "hello, world";
+/// assert_contains!(greeting, "world");
+/// assert_contains!(greeting, ',');
+/// assert_contains!(vec![1, 2, 3], 2, "2 went missing");
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! assert_contains {
+    ($haystack:expr, $needle:expr) => ({
+        match (&$haystack, &$needle) {
+            (haystack, needle) => {
+                if !$crate::rt::macro_support::Contains::contains_needle(&haystack[], needle) {
+                    panic!("assertion failed: `{}` does not contain `{}` \
+                            (haystack: `{:?}`, needle: `{:?}`)",
+                           stringify!($haystack), stringify!($needle), *haystack, *needle)
+                }
+            }
+        }
+    });
+    ($haystack:expr, $needle:expr, $($arg:tt)+) => ({
+        match (&$haystack, &$needle) {
+            (haystack, needle) => {
+                if !$crate::rt::macro_support::Contains::contains_needle(&haystack[], needle) {
+                    panic!("assertion failed: `{}` does not contain `{}` \
+                            (haystack: `{:?}`, needle: `{:?}`): {}",
+                           stringify!($haystack), stringify!($needle), *haystack, *needle,
+                           format_args!($($arg)+))
+                }
+            }
+        }
+    });
+}
+
+/// Maps a closure over an iterable with one thread per element.
+///
+/// `spawn_collect!(items, |x| work(x))` spawns a scoped thread for each
+/// element of `items`, which may be anything implementing `IntoIterator`,
+/// and calls the closure with the element on that thread. Once every thread
+/// has been spawned they are all joined, and the result is a `Vec` with each
+/// thread's `thread::Result` in the order of the elements: `Ok` of what the
+/// closure returned, or `Err` of the panic's payload if it panicked. A panic
+/// on one thread doesn't affect the others.
+///
+/// This really does start one OS thread per element, all at once; it is
+/// meant for a handful of slow, independent jobs, not as a thread pool. The
+/// elements and the closure's results must be `Send`, and the closure itself
+/// `Sync`, since every thread shares it. It may borrow from the enclosing
+/// scope, as the threads have all finished by the time the macro returns.
+///
+/// # Example
+///
+/// ```
+/// let lens = spawn_collect!(vec!["a", "bb", "ccc"], |s: &str| s.len());
+/// let lens = lens.into_iter().map(|r| r.ok().unwrap()).collect::<Vec<_>>();
+/// assert_eq!(lens, vec![1, 2, 3]);
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! spawn_collect {
+    ($items:expr, $f:expr) => (
+        $crate::rt::macro_support::spawn_collect($crate::iter::IntoIterator::into_iter($items),
+                                                 $f)
+    );
+}
+
+/// Prints key/value pairs to the task's stderr handle, with the values lined
+/// up.
+///
+/// `print_kv!{ "host" => host, "port" => port }` prints one line per pair,
+/// with the key, a colon, and then the value. The keys are padded to the
+/// width of the longest, counted in characters, so that the values start in
+/// the same column. Keys and values may be of any type implementing
+/// `Display`, and are only borrowed.
+///
+/// # Example
+///
+/// ```
+/// let (host, port) = ("localhost", 8080);
+/// print_kv!{ "host" => host, "port" => port, "user name" => "admin" };
+/// // host:      localhost
+/// // port:      8080
+/// // user name: admin
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! print_kv {
+    ($($key:expr => $value:expr),*) => (
+        $crate::rt::macro_support::print_kv(
+            &[$((&$key as &$crate::fmt::Display, &$value as &$crate::fmt::Display)),*])
+    );
+    ($($key:expr => $value:expr),+,) => (print_kv!($($key => $value),+));
+}
+
+/// Gives the first of several `Option`s which is `Some`.
+///
+/// `coalesce!(a, b, c)` evaluates its arguments in order until one of them is
+/// `Some`, and gives that option; the arguments after it aren't evaluated at
+/// all. If every argument is `None`, so is the result. All of the arguments
+/// must have the same `Option` type. This is like SQL's `COALESCE`, and like
+/// `a.or_else(|| b).or_else(|| c)`.
+///
+/// # Example
+///
+/// ```
+/// use std::env;
+///
+/// let port = coalesce!(env::var("PORT").ok(), Some("8080".to_string()));
+/// assert!(port.is_some());
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! coalesce {
+    ($last:expr) => ($last);
+    ($first:expr, $($rest:expr),+) => (
+        match $first {
+            $crate::option::Option::Some(v) => $crate::option::Option::Some(v),
+            $crate::option::Option::None => coalesce!($($rest),+),
+        }
+    );
+    ($($x:expr),+,) => (coalesce!($($x),+));
+}
+
+/// Retries an operation for as long as it fails with retryable errors.
+///
+/// `retry_if!(max, |e| retryable(e), expr)` evaluates `expr`, which must give
+/// a `Result`. If it is `Err`, the closure is called with a reference to the
+/// error, and if it returns `true`, `expr` is evaluated again, up to `max`
+/// attempts in all. The first `Ok`, the first error the closure rejects, or
+/// the result of the last attempt is the value of the macro. `expr` is
+/// always evaluated at least once, even if `max` is zero.
+///
+/// `retry_if!(max, |e| retryable(e), expr, interval)` also sleeps for the
+/// `Duration` `interval` before each retry.
+///
+/// # Example
+///
+/// ```
+/// use std::cell::Cell;
+///
+/// let attempts = Cell::new(0);
+/// let r = retry_if!(5, |e: &&str| *e == "busy", {
+///     attempts.set(attempts.get() + 1);
+///     if attempts.get() < 3 { Err("busy") } else { Ok(attempts.get()) }
+/// });
+/// assert_eq!(r, Ok(3));
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! retry_if {
+    ($max:expr, $retryable:expr, $e:expr) => (
+        $crate::rt::macro_support::retry_if($max, $retryable, $crate::option::Option::None,
+                                            || $e)
+    );
+    ($max:expr, $retryable:expr, $e:expr, $interval:expr) => (
+        $crate::rt::macro_support::retry_if($max, $retryable,
+                                            $crate::option::Option::Some($interval), || $e)
+    );
+}
+
+/// Joins the `Display` forms of its arguments with a separator.
+///
+/// `join_str!(sep, a, b, c)` gives the same string as
+/// `format!("{}{}{}{}{}", a, sep, b, sep, c)`: the separator goes between
+/// each pair of arguments, with none after the last, so a single argument is
+/// just formatted on its own. The arguments are only borrowed, and each is
+/// evaluated and formatted exactly once, straight into the result. The
+/// lengths of the separators, and of the arguments which are string slices,
+/// `String`s or `char`s, are added up first and reserved up front, so joining
+/// only those allocates once, at exactly the size needed. Use `fmt_list!` to
+/// join the items of a collection instead.
+///
+/// # Example
+///
+/// ```
+/// let host = "localhost";
+/// assert_eq!(join_str!(", ", host, 8080, true), "localhost, 8080, true");
+/// assert_eq!(join_str!(", ", host), "localhost");
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! join_str {
+    ($sep:expr, $($x:expr),+) => ({
+        #[allow(unused_imports)]
+        use $crate::rt::macro_support::{DisplayPiece, StrPiece};
+        $crate::rt::macro_support::join_display(
+            $sep, &[$((&$crate::rt::macro_support::Piece(&$x)).piece()),+])
+    });
+    ($sep:expr, $($x:expr),+,) => (join_str!($sep, $($x),+));
+}
+
+/// Writes the alternate `Debug` form of a value into a writer.
+///
+/// `pwrite!(dst, x)` is the same as `write!(dst, "{:#?}", x)`: `dst` may be
+/// anything `write!` accepts, either a `fmt::Writer` such as a `String` or an
+/// `old_io::Writer` such as a file or a `Vec<u8>`, and the writer's `Result`
+/// is returned. Nothing is formatted into an intermediate `String` first.
+///
+/// # Example
+///
+/// ```
+/// # #![allow(unused_must_use)]
+/// let mut w = Vec::new();
+/// pwrite!(&mut w, Some((1, "one")));
+/// assert_eq!(w, format!("{:#?}", Some((1, "one"))).into_bytes());
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! pwrite {
+    ($dst:expr, $x:expr) => (write!($dst, "{:#?}", $x))
+}
+
+/// Busy-waits on a condition, for at most a given number of spins.
+///
+/// `spin_until!(max_spins, || cond)` calls the closure in a tight loop, up to
+/// `max_spins` times, with a spin-wait hint to the processor between calls
+/// where it has one. It evaluates to `true` as soon as the closure does, or to
+/// `false` once the spins run out. Unlike `retry_until!`, the thread never
+/// sleeps or yields, so this is only worth it when the wait is expected to be
+/// very short.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::atomic::{AtomicBool, Ordering, ATOMIC_BOOL_INIT};
+/// use std::thread::Thread;
+///
+/// static READY: AtomicBool = ATOMIC_BOOL_INIT;
+///
+/// let t = Thread::scoped(|| READY.store(true, Ordering::SeqCst));
+/// t.join().ok().unwrap();
+/// assert!(spin_until!(1000, || READY.load(Ordering::SeqCst)));
+/// assert!(!spin_until!(1000, || false));
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! spin_until {
+    ($max_spins:expr, $cond:expr) => (
+        $crate::rt::macro_support::spin_until($max_spins, $cond)
+    );
+}
+
+/// Escapes a string the way `Debug` does, without the surrounding quotes.
+///
+/// `escape!(s)` returns a new `String` in which every character of `s` has
+/// been escaped with `char::escape_default`, just as it would be by
+/// `format!("{:?}", s)`: quotes, backslashes, tabs, carriage returns and
+/// newlines get a backslash, other ASCII control characters and all non-ASCII
+/// characters are written as `\u{...}` escapes, and printable ASCII is left as
+/// it is. The string may be a `&str` or a `String`, and is only borrowed.
+///
+/// # Example
+///
+/// ```
+/// let s = "say \"hi\"\n";
+/// assert_eq!(escape!(s), r#"say \"hi\"\n"#);
+/// assert_eq!(format!("\"{}\"", escape!(s)), format!("{:?}", s));
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! escape {
+    ($s:expr) => ($crate::str::StrExt::escape_default(&$s[]));
+}
+
+/// Takes the contents of a vector, leaving an empty one in its place.
+///
+/// `take_vec!(v)` is the same as `mem::replace(&mut v, Vec::new())`: it
+/// evaluates to the vector which was at `v`, and leaves `v` empty. `v` must
+/// be a place which can be mutably borrowed, such as a local, a field, or
+/// `*r` for a `r: &mut Vec<T>`. The new vector doesn't allocate.
+///
+/// # Example
+///
+/// ```
+/// fn flush(pending: &mut Vec<u32>) -> Vec<u32> {
+///     take_vec!(*pending)
+/// }
+///
+/// let mut pending = vec![1, 2, 3];
+/// assert_eq!(flush(&mut pending), vec![1, 2, 3]);
+/// assert!(pending.is_empty());
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! take_vec {
+    ($place:expr) => ($crate::mem::replace(&mut $place, $crate::vec::Vec::new()));
+}
+
+/// Takes the value out of a place, leaving its default value behind.
+///
+/// `take!(x)` is the same as `mem::replace(&mut x, Default::default())`, for
+/// a place of any type implementing `Default`. See `take_vec!`.
+///
+/// # Example
+///
+/// ```
+/// let mut name = "pending".to_string();
+/// assert_eq!(take!(name), "pending");
+/// assert_eq!(name, "");
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! take {
+    ($place:expr) => ($crate::mem::replace(&mut $place, $crate::default::Default::default()));
+}
+
+/// Prints a backtrace of the current thread, in debug builds only.
+///
+/// `print_backtrace!()` writes the call stack to the task's stderr handle,
+/// in the same form as a panic with `RUST_BACKTRACE` set, after the file and
+/// line of the call. Nothing panics and the thread carries on as before. On
+/// platforms where no backtrace can be taken, a single line saying that the
+/// backtrace is unavailable is printed instead.
+///
+/// When compiling with `--cfg ndebug` nothing is printed, unless the
+/// backtrace is asked for with `print_backtrace!(force)`.
+///
+/// # Example
+///
+/// ```
+/// fn checkpoint() {
+///     print_backtrace!();
+/// }
+///
+/// checkpoint();
+/// // prints something like:
+/// // src/main.rs:2: stack backtrace:
+/// //    1:     0x7f3c2b1e4d20 - checkpoint::h2a1b2c3d4e5f6a7b
+/// //    ...
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! print_backtrace {
+    () => ({
+        if cfg!(not(ndebug)) {
+            print_backtrace!(force)
+        }
+    });
+    (force) => ({
+        static _FILE_LINE: (&'static str, usize) = (file!(), line!());
+        $crate::rt::macro_support::print_backtrace(&_FILE_LINE)
+    });
+}
+
+/// Asserts that a block runs to completion without panicking.
+///
+/// `assert_no_panic!({ ... })` runs the block and evaluates to its value. If
+/// the block panics, the assertion panics in turn, with a message giving the
+/// file and line of the assertion and the original panic's message, as
+/// recovered by `capture_panic_message!`.
+///
+/// As with `capture_panic_message!`, the block is run on the current thread,
+/// so it may use anything in the enclosing scope, `Send` or not.
+///
+/// # Example
+///
+/// ```
+/// let v = vec![1, 2, 3];
+/// let second = assert_no_panic!({ v[1] });
+/// assert_eq!(second, 2);
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! assert_no_panic {
+    ($body:block) => (
+        match $crate::rt::macro_support::capture_panic_message(|| $body) {
+            $crate::result::Result::Ok(value) => value,
+            $crate::result::Result::Err(msg) => {
+                panic!("assertion failed: block panicked at {}:{}: {}", file!(), line!(), msg)
+            }
+        }
+    );
+}
+
+/// Keeps a formatted message in a fixed-size buffer of recent messages.
+///
+/// `ring_log!(capacity, "format {}", args)` formats its arguments as
+/// `format!` does, and appends the message to a buffer belonging to this call
+/// of `ring_log!`. The buffer keeps only the last `capacity` messages logged
+/// here, dropping the oldest to make room, so a call in a hot loop uses a
+/// bounded amount of memory. Each call site has its own buffer, shared by
+/// every thread which logs through it.
+///
+/// `dump_ring_log!()` prints the messages kept by every call site, one per
+/// line, to the task's stderr handle, in the order in which they were logged.
+/// The buffers are left as they are.
+///
+/// # Example
+///
+/// ```
+/// for i in 0..100 {
+///     ring_log!(3, "step {}", i);
+/// }
+/// dump_ring_log!();
+/// // prints:
+/// // step 97
+/// // step 98
+/// // step 99
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! ring_log {
+    ($capacity:expr, $($arg:tt)+) => ({
+        static _SITE: $crate::rt::macro_support::RingLogSite =
+            $crate::rt::macro_support::RING_LOG_SITE_INIT;
+        _SITE.push($capacity, format!($($arg)+))
+    });
+}
+
+/// Prints the messages kept by `ring_log!`.
+///
+/// See `ring_log!`.
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! dump_ring_log {
+    () => ($crate::rt::macro_support::dump_ring_log());
+}
+
+/// Maps the value in an `Option`, or gives a default if there is none.
+///
+/// `map_or!(opt, default, |x| expr)` gives the result of calling the closure
+/// (or any other function) on the value in `opt` if it is `Some`, and
+/// `default` if it is `None`. Unlike `Option::map_or`, `default` is only
+/// evaluated when `opt` is `None`, so it may be expensive or have side
+/// effects. `opt` is moved into the match, as for `Option::map_or`; use
+/// `opt.as_ref()` to map a reference to the value instead.
+///
+/// `map_or_else!(opt, || default, |x| expr)` is the same, except that the
+/// default is computed by calling the first closure, as for
+/// `Option::map_or_else`.
+///
+/// # Example
+///
+/// ```
+/// let name = Some("ferris");
+/// assert_eq!(map_or!(name, 0, |s: &str| s.len()), 6);
+///
+/// let none: Option<&str> = None;
+/// assert_eq!(map_or!(none, "anonymous".len(), |s: &str| s.len()), 9);
+/// assert_eq!(map_or_else!(none, || 42, |s: &str| s.len()), 42);
+/// ```
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! map_or {
+    ($opt:expr, $default:expr, $f:expr) => (
+        match $opt {
+            $crate::option::Option::Some(v) => ($f)(v),
+            $crate::option::Option::None => $default,
+        }
+    );
+}
+
+/// Maps the value in an `Option`, or computes a default if there is none.
+///
+/// See `map_or!`.
+#[macro_export]
+#[unstable(feature = "std_misc")]
+macro_rules! map_or_else {
+    ($opt:expr, $default:expr, $f:expr) => (
+        match $opt {
+            $crate::option::Option::Some(v) => ($f)(v),
+            $crate::option::Option::None => ($default)(),
+        }
+    );
+}
+
 // When testing the standard library, we link to the liblog crate to get the
 // logging macros. In doing so, the liblog crate was linked against the real
 // version of libstd, and uses a different std::fmt module than the test crate
@@ -245,6 +3500,50 @@ pub mod builtin {
     #[macro_export]
     macro_rules! option_env { ($name:expr) => ({ /* compiler built-in */ }) }
 
+    /// Parse an environment variable at compile time.
+    ///
+    /// This macro reads the named environment variable at compile time and
+    /// parses it as the given primitive type, expanding to a literal of that
+    /// type. The type may be any integer or floating point type, `bool`, or
+    /// `char`. Because the result is a literal it can be used anywhere a
+    /// constant is expected, such as the length of an array.
+    ///
+    /// A compilation error is emitted if the variable is not defined or if its
+    /// value cannot be parsed. The second form takes a default expression
+    /// which is used instead when the variable is not defined; a defined but
+    /// unparseable value is still an error.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// const MAX_CONNECTIONS: u32 = env_parse!("MAX_CONNECTIONS": u32 = 16);
+    /// println!("allowing up to {} connections", MAX_CONNECTIONS);
+    /// ```
+    #[macro_export]
+    macro_rules! env_parse {
+        ($name:tt: $t:ty) => ({ /* compiler built-in */ });
+        ($name:tt: $t:ty = $default:expr) => ({ /* compiler built-in */ })
+    }
+
+    /// Check whether an environment variable enables a flag at compile time.
+    ///
+    /// This expands to the literal `true` if the named environment variable
+    /// is set to `1` or `true` at compile time, and to `false` otherwise,
+    /// including when it is not set at all. As a literal, it can be used to
+    /// initialize a `const`, and branches on it are trivially removed by the
+    /// optimizer.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// const VERBOSE: bool = env_bool!("MYAPP_VERBOSE");
+    /// if VERBOSE {
+    ///     println!("verbose output enabled");
+    /// }
+    /// ```
+    #[macro_export]
+    macro_rules! env_bool { ($name:expr) => ({ /* compiler built-in */ }) }
+
     /// Concatenate identifiers into one identifier.
     ///
     /// This macro takes any number of comma-separated identifiers, and
@@ -289,6 +3588,130 @@ pub mod builtin {
     #[macro_export]
     macro_rules! concat { ($($e:expr),*) => ({ /* compiler built-in */ }) }
 
+    /// Build a fixed-size lookup table from sparse `index => value` entries.
+    ///
+    /// The first argument is a default value; it is followed by a `;` and a
+    /// comma-separated list of entries whose indices are integer literals.
+    /// This expands to an array expression of length one more than the
+    /// largest index, in which every slot without an entry holds the default.
+    /// As the expansion is a plain array expression, it can be used to
+    /// initialize a `static` or `const` whenever the values themselves are
+    /// constants.
+    ///
+    /// Giving the same index twice, or an index of 4096 or more, is a
+    /// compilation error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// static NAMES: [&'static str; 5] = const_table!("unknown";
+    ///     0 => "zero",
+    ///     1 => "one",
+    ///     4 => "four",
+    /// );
+    ///
+    /// assert_eq!(NAMES[1], "one");
+    /// assert_eq!(NAMES[3], "unknown");
+    /// ```
+    #[macro_export]
+    macro_rules! const_table {
+        ($default:expr; $($index:expr => $value:expr),+) => ({ /* compiler built-in */ });
+        ($default:expr; $($index:expr => $value:expr),+,) => ({ /* compiler built-in */ })
+    }
+
+    /// Creates a NUL-terminated byte string from a string literal.
+    ///
+    /// This expands to a byte string literal, of type `&'static [u8]`, holding
+    /// the UTF-8 bytes of the given string followed by a single NUL byte. It
+    /// is intended for passing constant strings to C functions. A string
+    /// containing a NUL byte of its own is rejected at compile time, as C
+    /// would see it as the end of the string.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let s = cstr!("hello");
+    /// assert_eq!(s, b"hello\0");
+    /// ```
+    #[macro_export]
+    macro_rules! cstr { ($s:expr) => ({ /* compiler built-in */ }) }
+
+    /// Causes compilation to fail with the given error message.
+    ///
+    /// The message must be a string literal, and is reported at the place
+    /// where `compile_error!` is expanded. It is intended for macros which
+    /// detect misuse: an arm which matches the bad input can expand to
+    /// `compile_error!` with an explanation, rather than leaving the user with
+    /// whatever error the bad expansion would produce.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// macro_rules! half {
+    ///     (0) => (compile_error!("half! of zero is always zero"));
+    ///     ($e:expr) => ($e / 2);
+    /// }
+    ///
+    /// assert_eq!(half!(10), 5);
+    /// ```
+    #[macro_export]
+    macro_rules! compile_error { ($msg:expr) => ({ /* compiler built-in */ }) }
+
+    /// Reverses the bytes of a byte string literal, at compile time.
+    ///
+    /// `reverse_bytes!(b"abc")` expands to the array literal
+    /// `[b'c', b'b', b'a']`, of type `[u8; 3]`. Since it is an ordinary array
+    /// of literals it may be used in a `const` or `static`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// const MAGIC: [u8; 4] = reverse_bytes!(b"\x7fELF");
+    /// assert_eq!(MAGIC, [b'F', b'L', b'E', 0x7f]);
+    /// ```
+    #[macro_export]
+    macro_rules! reverse_bytes { ($s:expr) => ({ /* compiler built-in */ }) }
+
+    /// Computes the CRC-32 checksum of a byte string literal, at compile time.
+    ///
+    /// `crc32!(b"data")` expands to a `u32` literal holding the checksum, so
+    /// it may be used in a `const` or `static`, for instance to check data
+    /// loaded with `include_bytes!` against a known value. The checksum is
+    /// the common CRC-32 of zlib, PNG and Ethernet: the polynomial
+    /// `0x04c11db7` with the bits of each byte reflected (`0xedb88320` in
+    /// reversed form), an initial value of `0xffffffff`, and the result
+    /// inverted.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// const CHECK: u32 = crc32!(b"123456789");
+    /// assert_eq!(CHECK, 0xcbf43926);
+    /// ```
+    #[macro_export]
+    macro_rules! crc32 { ($s:expr) => ({ /* compiler built-in */ }) }
+
+    /// Computes the floor of the base-2 logarithm of an integer literal, at
+    /// compile time.
+    ///
+    /// `log2!(n)` expands to an unsuffixed integer literal holding the index
+    /// of the highest set bit of `n`, so `log2!(1024)` is `10` and
+    /// `log2!(1000)` is `9`. Since it is a literal it may be used in a `const`
+    /// or `static`, or as the length of an array type. The argument must be a
+    /// positive integer literal: `log2!(0)` is a compile-time error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// const BUCKETS: usize = 4096;
+    /// const SHIFT: u32 = log2!(4096);
+    /// static BITS: [bool; log2!(256)] = [false; 8];
+    /// assert_eq!(BUCKETS >> SHIFT as usize, 1);
+    /// assert_eq!(BITS.len(), 8);
+    /// ```
+    #[macro_export]
+    macro_rules! log2 { ($n:expr) => ({ /* compiler built-in */ }) }
+
     /// A macro which expands to the line number on which it was invoked.
     ///
     /// The expanded expression has type `usize`, and the returned line is not
@@ -418,4 +3841,27 @@ pub mod builtin {
     /// ```
     #[macro_export]
     macro_rules! cfg { ($cfg:tt) => ({ /* compiler built-in */ }) }
+
+    /// Includes a block only in debug builds.
+    ///
+    /// In a debug build this expands to the given block, run as a statement
+    /// so that the macro always evaluates to `()`. When compiling with
+    /// `--cfg ndebug` it expands to `()` alone: the block is thrown away
+    /// before it is resolved or type checked, so it may freely refer to items
+    /// which only exist under `#[cfg(not(ndebug))]`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// #[cfg(not(ndebug))]
+    /// fn check_invariants(v: &[u32]) -> bool { v.len() < 100 }
+    ///
+    /// let v = vec![1, 2, 3];
+    /// debug_only!({
+    ///     assert!(check_invariants(&v[]));
+    ///     println!("checked {} elements", v.len());
+    /// });
+    /// ```
+    #[macro_export]
+    macro_rules! debug_only { ($body:block) => ({ /* compiler built-in */ }) }
 }
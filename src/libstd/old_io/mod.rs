@@ -411,6 +411,9 @@ pub enum IoErrorKind {
     /// The operation temporarily failed (for example, because a signal was received), and retrying
     /// may succeed.
     ResourceUnavailable,
+    /// The operation was interrupted, for example by a signal, before it could
+    /// complete. It can simply be restarted.
+    Interrupted,
     /// No I/O functionality is available for this task.
     IoUnavailable,
     /// A parameter was incorrect in a way that caused an I/O error not part of this list.
@@ -1652,6 +1655,7 @@ pub fn standard_error(kind: IoErrorKind) -> IoError {
         TimedOut => "operation timed out",
         ShortWrite(..) => "short write",
         NoProgress => "no progress",
+        Interrupted => "operation interrupted",
     };
     IoError {
         kind: kind,
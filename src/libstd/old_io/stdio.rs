@@ -28,7 +28,7 @@
 use self::StdSource::*;
 
 use boxed::Box;
-use cell::RefCell;
+use cell::{Cell, RefCell};
 use clone::Clone;
 use failure::LOCAL_STDERR;
 use fmt;
@@ -39,8 +39,9 @@ use libc;
 use mem;
 use option::Option;
 use option::Option::{Some, None};
-use ops::{Deref, DerefMut, FnOnce};
+use ops::{Deref, DerefMut, Drop, FnOnce};
 use ptr;
+use raw;
 use result::Result::{Ok, Err};
 use rt;
 use slice::SliceExt;
@@ -48,6 +49,7 @@ use str::StrExt;
 use string::String;
 use sys::{fs, tty};
 use sync::{Arc, Mutex, MutexGuard, Once, ONCE_INIT};
+use thread_local::State;
 use usize;
 use vec::Vec;
 
@@ -99,6 +101,42 @@ thread_local! {
     }
 }
 
+thread_local! {
+    static LOCAL_STDIN: RefCell<Option<Box<Buffer + Send>>> = {
+        RefCell::new(None)
+    }
+}
+
+// The address of the task's default stdout handle, or 0 if it has none. This
+// tells the process's stdout apart from the handles given to `set_stdout`, even
+// once the default has been handed back to `set_stdout` to restore it.
+thread_local! {
+    static DEFAULT_STDOUT: Cell<usize> = Cell::new(0)
+}
+
+// The default stdout handle which `with_task_stdout` stores for a task. It
+// forgets its address when dropped, so that a handle which is later allocated
+// in its place isn't taken for it.
+struct DefaultStdout(LineBufferedWriter<StdWriter>);
+
+impl Writer for DefaultStdout {
+    fn write_all(&mut self, buf: &[u8]) -> IoResult<()> { self.0.write_all(buf) }
+    fn flush(&mut self) -> IoResult<()> { self.0.flush() }
+}
+
+impl Drop for DefaultStdout {
+    fn drop(&mut self) {
+        if DEFAULT_STDOUT.state() == State::Destroyed { return }
+        let me = self as *mut DefaultStdout as usize;
+        DEFAULT_STDOUT.with(|addr| if addr.get() == me { addr.set(0) });
+    }
+}
+
+fn handle_addr(handle: &Writer) -> usize {
+    let obj: raw::TraitObject = unsafe { mem::transmute(handle) };
+    obj.data as usize
+}
+
 struct RaceBox(BufferedReader<StdReader>);
 
 unsafe impl Send for RaceBox {}
@@ -292,6 +330,21 @@ pub fn stderr_raw() -> StdWriter {
     src(libc::STDERR_FILENO, false, |src| StdWriter { inner: src })
 }
 
+/// Resets the task-local stdin handle to the specified reader
+///
+/// This will replace the current task's stdin handle, returning the old
+/// handle. All future calls to `read_line!` will read their input from this
+/// specified handle.
+///
+/// Note that this does not affect `stdin()`, which always reads from the
+/// process's stdin stream, as does `read_line!` when no handle has been set.
+pub fn set_stdin(stdin: Box<Buffer + Send>) -> Option<Box<Buffer + Send>> {
+    let mut new = Some(stdin);
+    LOCAL_STDIN.with(|slot| {
+        mem::replace(&mut *slot.borrow_mut(), new.take())
+    })
+}
+
 /// Resets the task-local stdout handle to the specified writer
 ///
 /// This will replace the current task's stdout handle, returning the old
@@ -342,7 +395,9 @@ fn with_task_stdout<F>(f: F) where F: FnOnce(&mut Writer) -> IoResult<()> {
     let mut my_stdout = LOCAL_STDOUT.with(|slot| {
         slot.borrow_mut().take()
     }).unwrap_or_else(|| {
-        box stdout() as Box<Writer + Send>
+        let default = box DefaultStdout(stdout());
+        DEFAULT_STDOUT.with(|addr| addr.set(&*default as *const DefaultStdout as usize));
+        default as Box<Writer + Send>
     });
     let result = f(&mut *my_stdout);
     let mut var = Some(my_stdout);
@@ -355,6 +410,81 @@ fn with_task_stdout<F>(f: F) where F: FnOnce(&mut Writer) -> IoResult<()> {
     }
 }
 
+// Helper to access the local task's stderr handle, like `with_task_stdout`.
+//
+// Unlike stdout, a default stderr handle is not stored in the task-local slot
+// when none has been set, as the panic machinery treats a set stderr handle
+// differently.
+fn with_task_stderr<F>(f: F) -> IoResult<()> where F: FnOnce(&mut Writer) -> IoResult<()> {
+    match LOCAL_STDERR.with(|slot| slot.borrow_mut().take()) {
+        Some(mut my_stderr) => {
+            let result = f(&mut *my_stderr);
+            let mut var = Some(my_stderr);
+            LOCAL_STDERR.with(|slot| {
+                *slot.borrow_mut() = var.take();
+            });
+            result
+        }
+        None => f(&mut stderr()),
+    }
+}
+
+/// Prints formatted output to the local task's stderr handle.
+///
+/// Errors are ignored, as there is nowhere left to report them. This is used
+/// by the debugging macros in the standard library.
+#[doc(hidden)]
+pub fn eprint_args(fmt: fmt::Arguments) {
+    let _ = with_task_stderr(|io| write!(io, "{}", fmt));
+}
+
+/// Like `eprint_args`, but followed by a newline.
+#[doc(hidden)]
+pub fn eprintln_args(fmt: fmt::Arguments) {
+    let _ = with_task_stderr(|io| writeln!(io, "{}", fmt));
+}
+
+/// Returns whether the local task's stdout handle goes to a terminal.
+///
+/// This is false while the handle is one given to `set_stdout`, unless it is
+/// the task's default handle being restored.
+#[doc(hidden)]
+pub fn stdout_isatty() -> bool {
+    let default = LOCAL_STDOUT.with(|slot| match *slot.borrow() {
+        Some(ref handle) => DEFAULT_STDOUT.with(|addr| addr.get() == handle_addr(&**handle)),
+        None => true,
+    });
+    default && stdout_raw().isatty()
+}
+
+/// Reads a line from the local task's stdin handle, without its line ending.
+///
+/// This is used by `read_line!`. If no handle has been set with `set_stdin`,
+/// the line is read from the process's stdin.
+#[doc(hidden)]
+pub fn read_task_line() -> IoResult<String> {
+    let line = match LOCAL_STDIN.with(|slot| slot.borrow_mut().take()) {
+        Some(mut my_stdin) => {
+            let result = my_stdin.read_line();
+            let mut var = Some(my_stdin);
+            LOCAL_STDIN.with(|slot| {
+                *slot.borrow_mut() = var.take();
+            });
+            result
+        }
+        None => stdin().read_line(),
+    };
+    line.map(|mut line| {
+        if line.ends_with("\n") {
+            line.pop();
+            if line.ends_with("\r") {
+                line.pop();
+            }
+        }
+        line
+    })
+}
+
 /// Flushes the local task's stdout handle.
 ///
 /// By default, this stream is a line-buffering stream, so flushing may be
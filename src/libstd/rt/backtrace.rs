@@ -37,6 +37,30 @@ pub fn log_enabled() -> bool {
     val == 2
 }
 
+/// The payload of a panic started with `panic_bt!`.
+///
+/// This can be recovered at a catch boundary (such as the `Err` of
+/// `JoinGuard::join`) by downcasting the panic's `Box<Any>`.
+pub struct PanicBacktrace {
+    /// The formatted panic message.
+    pub msg: String,
+    /// The backtrace of the panicking thread, as printed with
+    /// `RUST_BACKTRACE` set. This is empty if no backtrace could be taken.
+    pub backtrace: String,
+}
+
+/// Captures a backtrace of the current thread as a string.
+///
+/// If backtraces are not supported on this platform, or the trace could not
+/// be taken, this returns an empty string.
+pub fn capture() -> String {
+    let mut w = Vec::new();
+    match write(&mut w) {
+        Ok(()) => String::from_utf8_lossy(&w[]).into_owned(),
+        Err(..) => String::new(),
+    }
+}
+
 #[cfg(test)]
 mod test {
     use prelude::v1::*;
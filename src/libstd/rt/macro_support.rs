@@ -0,0 +1,1265 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Support code for the macros exported by the standard library.
+//!
+//! Nothing in here is intended to be used directly; these are the pieces of
+//! the standard macros which are too large (or need too many trait bounds) to
+//! be expanded inline at every call site.
+
+use prelude::v1::*;
+
+use any::Any;
+use cell::Cell;
+use cmp;
+use collections::RingBuf;
+use collections::hash_map::{Entry, HashMap, Hasher};
+use env::{self, ParseVarError};
+use fmt;
+use hash::Hash;
+use i64;
+use io;
+use iter::{self, FromIterator, IntoIterator};
+use mem;
+use num::{Float, Int, NumCast};
+use ops::{Deref, DerefMut, Range, RangeFrom, RangeFull, RangeTo};
+use old_io::{self, stdio, timer, IoError, Timer};
+use rt::{self, backtrace};
+use str::{CharEq, FromStr};
+use sync::{StaticMutex, MUTEX_INIT};
+use sync::atomic::{AtomicUsize, Ordering, ATOMIC_USIZE_INIT};
+use sync::mpsc::{channel, Receiver, RecvError, Select};
+use sys::thread as sys_thread;
+use sys::time::SteadyTime;
+use thread::{self, JoinGuard, Thread};
+use time::Duration;
+
+/// A single row of a table printed by `print_table!`.
+///
+/// This is implemented for tuples and arrays of up to twelve `Display`
+/// columns, and for references to any row.
+pub trait TableRow {
+    /// Formats each column of this row with `Display`.
+    fn cells(&self) -> Vec<String>;
+}
+
+impl<'a, R: TableRow + ?Sized> TableRow for &'a R {
+    fn cells(&self) -> Vec<String> { (**self).cells() }
+}
+
+macro_rules! tuple_rows {
+    ($(($($T:ident),+))+) => {
+        $(
+            impl<$($T: fmt::Display),+> TableRow for ($($T,)+) {
+                #[allow(non_snake_case)]
+                fn cells(&self) -> Vec<String> {
+                    let ($(ref $T,)+) = *self;
+                    vec![$($T.to_string()),+]
+                }
+            }
+        )+
+    }
+}
+
+tuple_rows! {
+    (A)
+    (A, B)
+    (A, B, C)
+    (A, B, C, D)
+    (A, B, C, D, E)
+    (A, B, C, D, E, F)
+    (A, B, C, D, E, F, G)
+    (A, B, C, D, E, F, G, H)
+    (A, B, C, D, E, F, G, H, I)
+    (A, B, C, D, E, F, G, H, I, J)
+    (A, B, C, D, E, F, G, H, I, J, K)
+    (A, B, C, D, E, F, G, H, I, J, K, L)
+}
+
+macro_rules! array_rows {
+    ($($N:expr)+) => {
+        $(
+            impl<T: fmt::Display> TableRow for [T; $N] {
+                fn cells(&self) -> Vec<String> {
+                    self.iter().map(|cell| cell.to_string()).collect()
+                }
+            }
+        )+
+    }
+}
+
+array_rows! { 1 2 3 4 5 6 7 8 9 10 11 12 }
+
+/// Prints `header`, a separator line, and then each of `rows`.
+///
+/// Every column is padded to the widest cell in it (counting the header), and
+/// columns are separated by ` | `. The last column is not padded so lines do
+/// not end in trailing whitespace.
+pub fn print_table<I, R>(header: &[String], rows: I)
+    where I: Iterator<Item=R>, R: TableRow
+{
+    let rows = rows.map(|row| row.cells()).collect::<Vec<_>>();
+
+    let mut widths = header.iter().map(|h| h.chars().count()).collect::<Vec<_>>();
+    for row in &rows {
+        for (i, cell) in row.iter().enumerate() {
+            let width = cell.chars().count();
+            if i < widths.len() {
+                widths[i] = cmp::max(widths[i], width);
+            } else {
+                widths.push(width);
+            }
+        }
+    }
+
+    print_table_row(&widths, header);
+    let separator = widths.iter().map(|&w| {
+        String::from_iter(iter::repeat('-').take(w))
+    }).collect::<Vec<_>>();
+    println!("{}", separator.connect("-+-"));
+    for row in &rows {
+        print_table_row(&widths, row);
+    }
+}
+
+fn print_table_row(widths: &[usize], cells: &[String]) {
+    let mut line = String::new();
+    for (i, &width) in widths.iter().enumerate() {
+        let cell = cells.get(i).map(|s| &s[]).unwrap_or("");
+        if i > 0 {
+            line.push_str(" | ");
+        }
+        if i + 1 == widths.len() {
+            line.push_str(cell);
+        } else {
+            line.push_str(&format!("{:<1$}", cell, width)[]);
+        }
+    }
+    println!("{}", line);
+}
+
+/// Removes every element of `v` matching `pred`, returning them in order.
+///
+/// The predicate is run over every element before the vector is modified, so
+/// if it panics `v` is left untouched.
+pub fn drain_filter<T, F>(v: &mut Vec<T>, mut pred: F) -> Vec<T>
+    where F: FnMut(&T) -> bool
+{
+    let matches = v.iter().map(|x| pred(x)).collect::<Vec<_>>();
+    let removed = matches.iter().filter(|&&m| m).count();
+    if removed == 0 {
+        return Vec::new();
+    }
+
+    let old = mem::replace(v, Vec::with_capacity(matches.len() - removed));
+    let mut drained = Vec::with_capacity(removed);
+    for (x, m) in old.into_iter().zip(matches.into_iter()) {
+        if m { drained.push(x) } else { v.push(x) }
+    }
+    drained
+}
+
+/// Joins the `Display` forms of `items` with `sep`.
+pub fn fmt_list<I, T>(items: I, sep: &str) -> String
+    where I: Iterator<Item=T>, T: fmt::Display
+{
+    use fmt::Writer;
+
+    // We can't know how long each item is until it's formatted, but we do
+    // know how many separators there will be at least.
+    let (lower, _) = items.size_hint();
+    let separators = if lower > 0 { lower - 1 } else { 0 };
+    let mut s = String::with_capacity(separators * sep.len());
+    for (i, item) in items.enumerate() {
+        if i > 0 {
+            s.push_str(sep);
+        }
+        let _ = write!(&mut s, "{}", item);
+    }
+    s
+}
+
+/// Formats `items` one per line, each prefixed by its number counting from
+/// `from`, for `numbered_list!`.
+pub fn numbered_list<I, T>(items: I, from: usize) -> String
+    where I: Iterator<Item=T>, T: fmt::Display
+{
+    use fmt::Writer;
+
+    let mut s = String::new();
+    for (i, item) in items.enumerate() {
+        if i > 0 {
+            s.push('\n');
+        }
+        let _ = write!(&mut s, "{}. {}", from + i, item);
+    }
+    s
+}
+
+/// Returns the index of the first element of `s` which is not in order with
+/// the element before it, according to `in_order`.
+pub fn first_unsorted<T, F>(s: &[T], mut in_order: F) -> Option<usize>
+    where F: FnMut(&T, &T) -> bool
+{
+    (1..s.len()).find(|&i| !in_order(&s[i - 1], &s[i]))
+}
+
+/// Like `first_unsorted`, where elements are in order unless `cmp` says the
+/// first is greater than the second.
+pub fn first_unsorted_by<T, F>(s: &[T], mut cmp: F) -> Option<usize>
+    where F: FnMut(&T, &T) -> cmp::Ordering
+{
+    first_unsorted(s, |a, b| cmp(a, b) != cmp::Ordering::Greater)
+}
+
+/// Formats the contents of a collection of length `len` for the message of a
+/// failed `assert_len!`, or gives an empty string if it is too long to be
+/// worth reading.
+pub fn len_contents(coll: &fmt::Debug, len: usize) -> String {
+    if len <= 16 { format!(" (`{:?}`)", coll) } else { String::new() }
+}
+
+/// A haystack which can be searched for a needle, for `assert_contains!`.
+///
+/// Strings contain `&str`s, `String`s and `char`s, and slices contain their
+/// elements.
+pub trait Contains<Needle> {
+    /// Whether `needle` occurs in `self`.
+    fn contains_needle(&self, needle: &Needle) -> bool;
+}
+
+impl<'a> Contains<&'a str> for str {
+    fn contains_needle(&self, needle: &&'a str) -> bool { self.contains(*needle) }
+}
+
+impl Contains<String> for str {
+    fn contains_needle(&self, needle: &String) -> bool { self.contains(&needle[]) }
+}
+
+impl Contains<char> for str {
+    fn contains_needle(&self, needle: &char) -> bool { self.contains_char(*needle) }
+}
+
+impl<T: PartialEq> Contains<T> for [T] {
+    fn contains_needle(&self, needle: &T) -> bool { self.contains(needle) }
+}
+
+/// Collects the `Ok` values of `iter`, stopping at the first `Err`.
+pub fn try_collect<I, T, E>(iter: I) -> Result<Vec<T>, E>
+    where I: Iterator<Item=Result<T, E>>
+{
+    let (lower, _) = iter.size_hint();
+    let mut v = Vec::with_capacity(lower);
+    for x in iter {
+        match x {
+            Ok(x) => v.push(x),
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(v)
+}
+
+/// Counts the characters in the `Display` form of `x`, without keeping them.
+pub fn display_len<T: fmt::Display + ?Sized>(x: &T) -> usize {
+    use fmt::Writer;
+
+    struct Counter(usize);
+
+    impl Writer for Counter {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            self.0 += s.chars().count();
+            Ok(())
+        }
+    }
+
+    let mut counter = Counter(0);
+    let _ = write!(&mut counter, "{}", x);
+    counter.0
+}
+
+/// A tuple of guards for running threads, joined by `join_threads!` and
+/// `scoped_tasks!`.
+pub trait JoinAll {
+    /// A tuple of the results of each thread, in order.
+    type Output;
+
+    /// A tuple of the values each thread returned, in order.
+    type Values;
+
+    /// Joins every thread in turn, collecting their results.
+    fn join_all(self) -> Self::Output;
+
+    /// Joins every thread in turn, and then either gives their values or, if
+    /// any of them panicked, resumes the first of those panics in this thread.
+    fn join_all_or_panic(self, file_line: &(&'static str, usize)) -> Self::Values;
+}
+
+macro_rules! tuple_joins {
+    ($(($($T:ident),+))+) => {
+        $(
+            impl<'a, $($T: Send + 'a),+> JoinAll for ($(JoinGuard<'a, $T>,)+) {
+                type Output = ($(thread::Result<$T>,)+);
+                type Values = ($($T,)+);
+
+                #[allow(non_snake_case)]
+                fn join_all(self) -> ($(thread::Result<$T>,)+) {
+                    let ($($T,)+) = self;
+                    ($($T.join(),)+)
+                }
+
+                #[allow(non_snake_case)]
+                fn join_all_or_panic(self, file_line: &(&'static str, usize)) -> ($($T,)+) {
+                    let ($($T,)+) = self.join_all();
+                    ($(match $T {
+                        Ok(v) => v,
+                        Err(payload) => rt::resume_unwind(payload, file_line),
+                    },)+)
+                }
+            }
+        )+
+    }
+}
+
+tuple_joins! {
+    (A)
+    (A, B)
+    (A, B, C)
+    (A, B, C, D)
+    (A, B, C, D, E)
+    (A, B, C, D, E, F)
+    (A, B, C, D, E, F, G)
+    (A, B, C, D, E, F, G, H)
+    (A, B, C, D, E, F, G, H, I)
+    (A, B, C, D, E, F, G, H, I, J)
+    (A, B, C, D, E, F, G, H, I, J, K)
+    (A, B, C, D, E, F, G, H, I, J, K, L)
+}
+
+/// Runs `f` on a new thread, waiting at most `dur` for it to finish.
+///
+/// Returns `None` if `f` did not finish in time, or if it panicked. The thread
+/// is left running after a timeout.
+pub fn with_timeout<T, F>(dur: Duration, f: F) -> Option<T>
+    where T: Send + 'static, F: FnOnce() -> T + Send + 'static
+{
+    let (tx, rx) = channel();
+    Thread::spawn(move|| {
+        // Nobody may be listening any more, but that's fine.
+        let _ = tx.send(f());
+    });
+
+    // The timer has to outlive the wait, as dropping it cancels the timeout.
+    let mut timer = Timer::new().ok().expect("failed to create a timer");
+    let timeout = timer.oneshot(dur);
+    select! {
+        result = rx.recv() => result.ok(),
+        _ = timeout.recv() => None
+    }
+}
+
+/// A value which prints a message to stderr when it is dropped, created by
+/// `debug_print_on_drop!`.
+pub struct DropPrinter<T> {
+    name: &'static str,
+    value: T,
+    enabled: bool,
+}
+
+impl<T> DropPrinter<T> {
+    /// Wraps `value`, printing `dropping <name>` when it's dropped if
+    /// `enabled` is true.
+    pub fn new(name: &'static str, value: T, enabled: bool) -> DropPrinter<T> {
+        DropPrinter { name: name, value: value, enabled: enabled }
+    }
+}
+
+impl<T> Deref for DropPrinter<T> {
+    type Target = T;
+    fn deref(&self) -> &T { &self.value }
+}
+
+impl<T> DerefMut for DropPrinter<T> {
+    fn deref_mut(&mut self) -> &mut T { &mut self.value }
+}
+
+#[unsafe_destructor]
+impl<T> Drop for DropPrinter<T> {
+    fn drop(&mut self) {
+        if self.enabled {
+            stdio::eprintln_args(format_args!("dropping {}", self.name));
+        }
+    }
+}
+
+/// A guard which prints how long it was alive to stderr when it is dropped,
+/// created by `debug_timer!`.
+pub struct DebugTimer<'a> {
+    label: &'a str,
+    start: Option<SteadyTime>,
+}
+
+impl<'a> DebugTimer<'a> {
+    /// Starts timing, if `enabled` is true. A disabled timer never reads the
+    /// clock and prints nothing.
+    pub fn new(label: &'a str, enabled: bool) -> DebugTimer<'a> {
+        DebugTimer { label: label, start: if enabled { Some(SteadyTime::now()) } else { None } }
+    }
+}
+
+#[unsafe_destructor]
+impl<'a> Drop for DebugTimer<'a> {
+    fn drop(&mut self) {
+        if let Some(ref start) = self.start {
+            let elapsed = &SteadyTime::now() - start;
+            stdio::eprintln_args(format_args!("{}: {}", self.label, fmt_duration(elapsed)));
+        }
+    }
+}
+
+thread_local! {
+    static SCOPE_DEPTH: Cell<usize> = Cell::new(0)
+}
+
+/// A guard which traces entry to and exit from a scope, created by
+/// `debug_scope!`.
+pub struct DebugScope<'a> {
+    label: &'a str,
+    enabled: bool,
+}
+
+impl<'a> DebugScope<'a> {
+    /// Prints `> label` indented by the current depth, and goes one level
+    /// deeper, if `enabled` is true.
+    pub fn enter(label: &'a str, enabled: bool) -> DebugScope<'a> {
+        if enabled {
+            let depth = SCOPE_DEPTH.with(|d| { let depth = d.get(); d.set(depth + 1); depth });
+            stdio::eprintln_args(format_args!("{:2$}> {}", "", label, depth * 2));
+        }
+        DebugScope { label: label, enabled: enabled }
+    }
+}
+
+#[unsafe_destructor]
+impl<'a> Drop for DebugScope<'a> {
+    fn drop(&mut self) {
+        if self.enabled {
+            let depth = SCOPE_DEPTH.with(|d| { let depth = d.get() - 1; d.set(depth); depth });
+            stdio::eprintln_args(format_args!("{:2$}< {}", "", self.label, depth * 2));
+        }
+    }
+}
+
+/// Puts a saved value back into a place when dropped, for `scoped_set!`.
+pub struct Restore<T> {
+    place: *mut T,
+    old: Option<T>,
+}
+
+impl<T> Restore<T> {
+    /// Stores `new` in `place`, keeping the old value to be put back when the
+    /// returned guard is dropped.
+    ///
+    /// This is unsafe because `place` must remain valid, and must not be
+    /// borrowed, when the guard is dropped.
+    pub unsafe fn new(place: *mut T, new: T) -> Restore<T> {
+        let old = mem::replace(&mut *place, new);
+        Restore { place: place, old: Some(old) }
+    }
+}
+
+#[unsafe_destructor]
+impl<T> Drop for Restore<T> {
+    fn drop(&mut self) {
+        if let Some(old) = self.old.take() {
+            unsafe { *self.place = old; }
+        }
+    }
+}
+
+/// Prints a hexdump of `bytes` to the task's stderr handle, in the same format
+/// as `hexdump -C`.
+pub fn hexdump(bytes: &[u8]) {
+    use fmt::Writer;
+
+    for (row, chunk) in bytes.chunks(16).enumerate() {
+        let mut line = String::with_capacity(78);
+        let _ = write!(&mut line, "{:08x}  ", row * 16);
+        for i in 0..16 {
+            match chunk.get(i) {
+                Some(b) => { let _ = write!(&mut line, "{:02x} ", b); }
+                None => line.push_str("   "),
+            }
+            if i == 7 {
+                line.push(' ');
+            }
+        }
+        line.push_str(" |");
+        for &b in chunk {
+            line.push(if b >= 0x20 && b < 0x7f { b as char } else { '.' });
+        }
+        line.push('|');
+        stdio::eprintln_args(format_args!("{}", line));
+    }
+}
+
+/// Prints a backtrace of the current thread to the task's stderr handle,
+/// prefixed by the location of the `print_backtrace!` call.
+pub fn print_backtrace(file_line: &(&'static str, usize)) {
+    let (file, line) = *file_line;
+    let trace = backtrace::capture();
+    if trace.is_empty() {
+        stdio::eprintln_args(format_args!("{}:{}: backtrace unavailable", file, line));
+    } else {
+        stdio::eprint_args(format_args!("{}:{}: {}", file, line, trace));
+    }
+}
+
+/// A `ring_log!` call site, which is given a buffer of its own the first time
+/// it logs a message.
+pub struct RingLogSite {
+    // One more than the index of the site's buffer in `RingLogs::sites`, or
+    // zero if it has none yet. Only accessed with `RING_LOG_LOCK` held.
+    index: AtomicUsize,
+}
+
+/// An initializer for a `ring_log!` call site's `static`.
+pub const RING_LOG_SITE_INIT: RingLogSite = RingLogSite { index: ATOMIC_USIZE_INIT };
+
+/// The buffers of all `ring_log!` call sites. Every message is numbered as it
+/// is logged, so that the messages of different sites can be put back in
+/// order.
+struct RingLogs {
+    next: u64,
+    sites: Vec<RingBuf<(u64, String)>>,
+}
+
+static RING_LOG_LOCK: StaticMutex = MUTEX_INIT;
+static mut RING_LOGS: *mut RingLogs = 0 as *mut RingLogs;
+
+/// Gives the buffers of all `ring_log!` call sites, creating them if need be.
+/// `RING_LOG_LOCK` must be held.
+unsafe fn ring_logs() -> &'static mut RingLogs {
+    if RING_LOGS.is_null() {
+        let logs = Box::new(RingLogs { next: 0, sites: Vec::new() });
+        RING_LOGS = mem::transmute(logs);
+    }
+    &mut *RING_LOGS
+}
+
+impl RingLogSite {
+    /// Appends `msg` to the site's buffer, first dropping its oldest messages
+    /// so that no more than `capacity` are kept.
+    pub fn push(&self, capacity: usize, msg: String) {
+        let _g = RING_LOG_LOCK.lock();
+        let logs = unsafe { ring_logs() };
+        let mut index = self.index.load(Ordering::Relaxed);
+        if index == 0 {
+            logs.sites.push(RingBuf::new());
+            index = logs.sites.len();
+            self.index.store(index, Ordering::Relaxed);
+        }
+        let seq = logs.next;
+        logs.next += 1;
+
+        if capacity == 0 {
+            return
+        }
+        let buf = &mut logs.sites[index - 1];
+        while buf.len() >= capacity {
+            buf.pop_front();
+        }
+        buf.push_back((seq, msg));
+    }
+}
+
+/// Prints the messages kept by every `ring_log!` call site to the task's
+/// stderr handle, in the order in which they were logged, for
+/// `dump_ring_log!`.
+pub fn dump_ring_log() {
+    let _g = RING_LOG_LOCK.lock();
+    let logs = unsafe { ring_logs() };
+    let mut msgs = logs.sites.iter().flat_map(|buf| buf.iter()).collect::<Vec<_>>();
+    msgs.sort_by(|a, b| a.0.cmp(&b.0));
+    for &&(_, ref msg) in msgs.iter() {
+        stdio::eprintln_args(format_args!("{}", msg));
+    }
+}
+
+/// Prints `s` in chunks of `size` elements to the task's stderr handle, one
+/// chunk per line prefixed by the index of its first element, for
+/// `print_chunks!`.
+pub fn print_chunks<T: fmt::Debug>(s: &[T], size: usize) {
+    assert!(size > 0, "print_chunks! needs a chunk size of at least 1");
+    // Right-align the indices to the width of the last one.
+    let last = if s.is_empty() { 0 } else { (s.len() - 1) / size * size };
+    let width = format!("{}", last).len();
+    for (i, chunk) in s.chunks(size).enumerate() {
+        stdio::eprintln_args(format_args!("{:>2$}: {:?}", i * size, chunk, width));
+    }
+}
+
+/// Calls `cond` every `interval` until it returns true, giving up once
+/// `timeout` has passed.
+///
+/// The condition is always checked at least once, and once more when the
+/// timeout is reached.
+pub fn retry_until<F>(timeout: Duration, interval: Duration, mut cond: F) -> bool
+    where F: FnMut() -> bool
+{
+    let start = SteadyTime::now();
+    loop {
+        if cond() {
+            return true
+        }
+        let elapsed = &SteadyTime::now() - &start;
+        if elapsed >= timeout {
+            return false
+        }
+        // Don't sleep past the deadline.
+        timer::sleep(cmp::min(interval, timeout - elapsed));
+    }
+}
+
+/// Calls `cond` up to `max_spins` times in a tight loop until it returns true,
+/// for `spin_until!`.
+pub fn spin_until<F>(max_spins: usize, mut cond: F) -> bool where F: FnMut() -> bool {
+    for _ in 0..max_spins {
+        if cond() {
+            return true
+        }
+        cpu_relax();
+    }
+    false
+}
+
+/// Tells the processor that this is a spin-wait loop, where it has an
+/// instruction for that.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn cpu_relax() {
+    unsafe { asm!("pause" :::: "volatile") }
+}
+
+#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+fn cpu_relax() {}
+
+/// An I/O error which `retry_io!` can check for an interruption: either a
+/// `std::io::Error` or an `old_io::IoError`.
+pub trait IoInterrupted {
+    /// Whether the operation which failed with `self` was interrupted.
+    fn is_interrupted(&self) -> bool;
+}
+
+impl IoInterrupted for io::Error {
+    fn is_interrupted(&self) -> bool { self.kind() == io::ErrorKind::Interrupted }
+}
+
+impl IoInterrupted for IoError {
+    fn is_interrupted(&self) -> bool { self.kind == old_io::Interrupted }
+}
+
+/// Calls `f` up to `max` times, for as long as it fails with errors which
+/// `retryable` accepts, sleeping for `interval` (if any) between attempts,
+/// for `retry_if!`.
+pub fn retry_if<T, E, P, F>(max: usize, mut retryable: P, interval: Option<Duration>, mut f: F)
+                            -> Result<T, E>
+    where P: FnMut(&E) -> bool, F: FnMut() -> Result<T, E>
+{
+    let mut attempt = 1;
+    loop {
+        match f() {
+            Err(ref e) if attempt < max && retryable(e) => {}
+            result => return result,
+        }
+        attempt += 1;
+        if let Some(interval) = interval {
+            timer::sleep(interval);
+        }
+    }
+}
+
+/// Formats a number of bytes using the largest unit of `base` (which must be
+/// 1000 or 1024) that it is at least one of, to one decimal place.
+pub fn format_si(bytes: u64, base: u64) -> String {
+    static BINARY: [&'static str; 6] = ["KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+    static DECIMAL: [&'static str; 6] = ["kB", "MB", "GB", "TB", "PB", "EB"];
+
+    let units = match base {
+        1024 => &BINARY,
+        1000 => &DECIMAL,
+        _ => panic!("format_si! base must be 1000 or 1024, not {}", base),
+    };
+    if bytes < base {
+        return format!("{} B", bytes);
+    }
+
+    let mut unit = 0;
+    let mut scale = base;
+    while unit + 1 < units.len() && bytes / scale >= base {
+        unit += 1;
+        scale *= base;
+    }
+    // A count just under the next unit rounds up to `base` of this one, as in
+    // `1024.0 KiB`, so give it as one of the next unit instead.
+    let value = bytes as f64 / scale as f64;
+    if unit + 1 < units.len() && (value * 10.0).round() >= (base * 10) as f64 {
+        return format!("{:.1} {}", value / base as f64, units[unit + 1])
+    }
+    format!("{:.1} {}", value, units[unit])
+}
+
+/// Formats `value` and pads it with spaces to at least `width` characters,
+/// for `format_aligned!`.
+///
+/// The value is formatted on its own first, so that the padding is applied
+/// even if its `Display` implementation ignores the formatter's width.
+pub fn format_aligned(value: &fmt::Display, width: usize, left: bool) -> String {
+    let s = format!("{}", value);
+    if left { format!("{:<1$}", s, width) } else { format!("{:>1$}", s, width) }
+}
+
+/// Formats `d` compactly, as in `1h2m3.004s`, `3.004s` or `250us`.
+pub fn fmt_duration(d: Duration) -> String {
+    use fmt::Writer;
+
+    if d < Duration::zero() {
+        return format!("-{}", fmt_duration(-d));
+    }
+    if d < Duration::milliseconds(1) {
+        // This can't overflow, as it's less than a millisecond.
+        let ns = d.num_nanoseconds().unwrap();
+        return if ns >= 1000 { format!("{}us", ns / 1000) } else { format!("{}ns", ns) };
+    }
+
+    let ms = d.num_milliseconds();
+    let (hours, mins, secs, ms) = (ms / 3_600_000, ms / 60_000 % 60, ms / 1000 % 60, ms % 1000);
+    let mut s = String::new();
+    if hours > 0 {
+        let _ = write!(&mut s, "{}h", hours);
+    }
+    if hours > 0 || mins > 0 {
+        let _ = write!(&mut s, "{}m", mins);
+    }
+    let _ = write!(&mut s, "{}.{:03}s", secs, ms);
+    s
+}
+
+/// Waits at most `dur` for a message on `rx`.
+///
+/// Returns `Ok(Some(..))` with a message, `Ok(None)` on timeout, and
+/// `Err(RecvError)` if the channel is disconnected with nothing left in it.
+pub fn recv_timeout<T: Send>(rx: &Receiver<T>, dur: Duration) -> Result<Option<T>, RecvError> {
+    // As in `with_timeout`, the timer has to outlive the wait.
+    let mut timer = Timer::new().ok().expect("failed to create a timer");
+    let timeout = timer.oneshot(dur);
+
+    let sel = Select::new();
+    let mut msg = sel.handle(rx);
+    let mut timer_msg = sel.handle(&timeout);
+    unsafe {
+        msg.add();
+        timer_msg.add();
+    }
+    if sel.wait() == msg.id() {
+        msg.recv().map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
+/// Runs `f` `iters` times, printing and returning the mean time taken per
+/// iteration in nanoseconds.
+pub fn benchmark<T, F>(label: &str, iters: u64, mut f: F) -> f64 where F: FnMut() -> T {
+    assert!(iters > 0, "benchmark! needs at least one iteration");
+
+    let start = SteadyTime::now();
+    for _ in 0..iters {
+        black_box(f());
+    }
+    let elapsed = &SteadyTime::now() - &start;
+
+    // A benchmark taking long enough to overflow this deserves to be off.
+    let ns = elapsed.num_nanoseconds().unwrap_or(i64::MAX) as f64 / iters as f64;
+    stdio::eprintln_args(format_args!("{}: {} iters, {:.1} ns/iter", label, iters, ns));
+    ns
+}
+
+/// Times `fa` and `fb` over `iters` iterations each, as `benchmark` does, and
+/// then prints how much faster the first is than the second.
+pub fn bench_compare<A, B, FA, FB>(iters: u64, label_a: &str, fa: FA, label_b: &str, fb: FB)
+                                   -> (f64, f64)
+    where FA: FnMut() -> A, FB: FnMut() -> B
+{
+    let a = benchmark(label_a, iters, fa);
+    let b = benchmark(label_b, iters, fb);
+    stdio::eprintln_args(format_args!("{} vs {}: {:.2}x speedup", label_a, label_b, b / a));
+    (a, b)
+}
+
+/// An identity function which the optimizer can't see through, so that the
+/// work done to produce `dummy` isn't optimized away. This is the same as
+/// `test::black_box`.
+fn black_box<T>(dummy: T) -> T {
+    unsafe { asm!("" : : "r"(&dummy)) }
+    dummy
+}
+
+/// A delimiter which `kv_split!` can split a string at: a `char` or a `&str`.
+pub trait KvDelim {
+    /// Finds the first occurrence of the delimiter in `s`, as the byte
+    /// offsets of its start and end.
+    fn find_in(&self, s: &str) -> Option<(usize, usize)>;
+}
+
+impl KvDelim for char {
+    fn find_in(&self, s: &str) -> Option<(usize, usize)> {
+        s.find(*self).map(|i| (i, i + self.len_utf8()))
+    }
+}
+
+impl<'a> KvDelim for &'a str {
+    fn find_in(&self, s: &str) -> Option<(usize, usize)> {
+        s.find_str(*self).map(|i| (i, i + self.len()))
+    }
+}
+
+/// Splits `s` at the first occurrence of `delim`, into the parts before and
+/// after it.
+pub fn kv_split<'a, D: KvDelim>(s: &'a str, delim: D) -> Option<(&'a str, &'a str)> {
+    delim.find_in(s).map(|(start, end)| (&s[..start], &s[end..]))
+}
+
+/// A point in time, measured on the monotonic clock, made by `deadline!`.
+pub struct Deadline {
+    start: SteadyTime,
+    after: Duration,
+}
+
+impl Deadline {
+    /// The deadline `after` from now.
+    pub fn after(after: Duration) -> Deadline {
+        Deadline { start: SteadyTime::now(), after: after }
+    }
+
+    /// The time left until the deadline, which is negative once it has
+    /// passed.
+    pub fn remaining(&self) -> Duration {
+        self.after - (&SteadyTime::now() - &self.start)
+    }
+}
+
+/// Sleeps until `deadline` has passed.
+pub fn sleep_until(deadline: &Deadline) {
+    loop {
+        let remaining = deadline.remaining();
+        if remaining <= Duration::zero() {
+            return
+        }
+        // The timer works in whole milliseconds, so round up rather than
+        // waking just short of the deadline.
+        let ms = remaining.num_milliseconds();
+        let ms = if Duration::milliseconds(ms) < remaining { ms + 1 } else { ms };
+        timer::sleep(Duration::milliseconds(ms));
+    }
+}
+
+/// Runs a closure when dropped, but only if the thread is panicking, for
+/// `defer_on_unwind!`.
+pub struct UnwindGuard<F: FnOnce()> {
+    f: Option<F>,
+    // Whether the thread was already panicking when the guard was made, in
+    // which case a later drop isn't caused by a new panic.
+    was_panicking: bool,
+}
+
+impl<F: FnOnce()> UnwindGuard<F> {
+    pub fn new(f: F) -> UnwindGuard<F> {
+        UnwindGuard { f: Some(f), was_panicking: Thread::panicking() }
+    }
+}
+
+#[unsafe_destructor]
+impl<F: FnOnce()> Drop for UnwindGuard<F> {
+    fn drop(&mut self) {
+        if Thread::panicking() && !self.was_panicking {
+            if let Some(f) = self.f.take() {
+                f();
+            }
+        }
+    }
+}
+
+/// Spawns a thread which runs `f` after trying to pin itself to `cpu`.
+pub fn spawn_on_cpu<'a, T, F>(cpu: usize, f: F) -> JoinGuard<'a, T>
+    where T: Send + 'a, F: FnOnce() -> T + Send + 'a
+{
+    Thread::scoped(move || {
+        // Affinity is only a hint for benchmarking, so carry on without it.
+        unsafe { sys_thread::set_affinity(cpu); }
+        f()
+    })
+}
+
+/// Calls `f` on each of `items` on a scoped thread of its own, and gives the
+/// threads' results in the same order, for `spawn_collect!`.
+///
+/// Every thread is spawned before any is joined, so that they all run at
+/// once.
+pub fn spawn_collect<I, T, F>(items: I, f: F) -> Vec<thread::Result<T>>
+    where I: Iterator, I::Item: Send, T: Send, F: Fn(I::Item) -> T + Sync
+{
+    let f = &f;
+    let guards = items.map(|x| Thread::scoped(move || f(x))).collect::<Vec<_>>();
+    guards.into_iter().map(|guard| guard.join()).collect()
+}
+
+/// Converts between integer types, saturating at the bounds of the target type
+/// when the value is out of its range.
+pub fn clamp_cast<T: Int, U: Int>(n: T) -> U {
+    match NumCast::from(n) {
+        Some(u) => u,
+        None if n < Int::zero() => Int::min_value(),
+        None => Int::max_value(),
+    }
+}
+
+/// Groups the elements of `iter` by the keys `key` gives for them, keeping the
+/// elements in each group in the order they came.
+pub fn group_by<I, K, F>(iter: I, mut key: F) -> HashMap<K, Vec<I::Item>>
+    where I: Iterator, K: Hash<Hasher> + Eq, F: FnMut(&I::Item) -> K
+{
+    let mut groups = HashMap::new();
+    for x in iter {
+        match groups.entry(key(&x)) {
+            Entry::Occupied(mut e) => e.get_mut().push(x),
+            Entry::Vacant(e) => { e.insert(vec![x]); }
+        }
+    }
+    groups
+}
+
+/// Collects the items of the iterables `iter` yields into one vector, for
+/// `flatten_collect!`.
+///
+/// All of the inner iterators are created before any of their items are
+/// taken, so that the vector can be allocated from the sum of their size hints.
+pub fn flatten_collect<I, J>(iter: I) -> Vec<<J::IntoIter as Iterator>::Item>
+    where I: Iterator<Item=J>, J: IntoIterator
+{
+    let inner = iter.map(|x| x.into_iter()).collect::<Vec<_>>();
+    let lower = inner.iter().fold(0, |n, it| n.saturating_add(it.size_hint().0));
+    let mut v = Vec::with_capacity(lower);
+    for it in inner {
+        v.extend(it);
+    }
+    v
+}
+
+/// Calls `f` to write into a new string, for `build_string!`.
+///
+/// Writing to a `String` can only fail if a formatting trait implementation
+/// returns an error, which `format!` also ignores.
+pub fn build_string<F>(f: F) -> String where F: FnOnce(&mut String) -> fmt::Result {
+    let mut s = String::new();
+    let _ = f(&mut s);
+    s
+}
+
+/// An argument of `string!` or `join_str!`, borrowed so that it can be asked
+/// for its length.
+///
+/// Calling `piece` on a `&Piece` gives the argument as a `Display`, together
+/// with its length in bytes if that is known without formatting it. That is
+/// the case for `str`, `String` and `char`, which implement `StrPiece` and so
+/// are picked out when the call is resolved; anything else falls back to
+/// `DisplayPiece`, which only takes effect once the `&Piece` has been
+/// borrowed again, and has an unknown length of zero.
+pub struct Piece<'a, T: ?Sized + 'a>(pub &'a T);
+
+/// A `Piece` whose length is known without formatting it.
+pub trait StrPiece {
+    /// The piece, and its length in bytes.
+    fn piece(&self) -> (&fmt::Display, usize);
+}
+
+impl<'a> StrPiece for Piece<'a, str> {
+    fn piece(&self) -> (&fmt::Display, usize) { (&self.0, self.0.len()) }
+}
+
+impl<'a, 'b> StrPiece for Piece<'a, &'b str> {
+    fn piece(&self) -> (&fmt::Display, usize) { (self.0, self.0.len()) }
+}
+
+impl<'a> StrPiece for Piece<'a, String> {
+    fn piece(&self) -> (&fmt::Display, usize) { (self.0, self.0.len()) }
+}
+
+impl<'a, 'b> StrPiece for Piece<'a, &'b String> {
+    fn piece(&self) -> (&fmt::Display, usize) { (self.0, self.0.len()) }
+}
+
+impl<'a> StrPiece for Piece<'a, char> {
+    fn piece(&self) -> (&fmt::Display, usize) { (self.0, self.0.len_utf8()) }
+}
+
+/// A `Piece` of any other `Display` type, whose length isn't known until it
+/// has been formatted.
+pub trait DisplayPiece {
+    /// The piece, and zero for its length.
+    fn piece(&self) -> (&fmt::Display, usize);
+}
+
+impl<'a, 'b, T: ?Sized + fmt::Display> DisplayPiece for &'b Piece<'a, T> {
+    fn piece(&self) -> (&fmt::Display, usize) { (&self.0, 0) }
+}
+
+/// Concatenates the `Display` forms of `parts`, for `string!`.
+///
+/// The known lengths of the parts are reserved up front, and then each part is
+/// formatted straight into the string.
+pub fn concat_display(parts: &[(&fmt::Display, usize)]) -> String {
+    use fmt::Writer;
+
+    let len = parts.iter().fold(0, |n, &(_, len)| n + len);
+    let mut s = String::with_capacity(len);
+    for &(part, _) in parts.iter() {
+        let _ = write!(&mut s, "{}", part);
+    }
+    s
+}
+
+/// Joins the `Display` forms of `parts` with `sep` between each pair, for
+/// `join_str!`.
+///
+/// The known lengths of the parts and the separators are reserved up front,
+/// and then each part is formatted straight into the string.
+pub fn join_display(sep: &str, parts: &[(&fmt::Display, usize)]) -> String {
+    use fmt::Writer;
+
+    let separators = if parts.len() > 0 { parts.len() - 1 } else { 0 };
+    let len = parts.iter().fold(separators * sep.len(), |n, &(_, len)| n + len);
+    let mut s = String::with_capacity(len);
+    for (i, &(part, _)) in parts.iter().enumerate() {
+        if i > 0 {
+            s.push_str(sep);
+        }
+        let _ = write!(&mut s, "{}", part);
+    }
+    s
+}
+
+/// Whether terminal escapes should be written to the task's stdout: when it is
+/// a terminal, or when the `CLICOLOR_FORCE` environment variable is set to
+/// anything but `0`.
+fn escapes_wanted() -> bool {
+    let force = match env::var("CLICOLOR_FORCE") {
+        Ok(v) => v != "0",
+        Err(..) => false,
+    };
+    force || stdio::stdout_isatty()
+}
+
+/// Prints a line to the task's stdout handle in the colour set by the ANSI
+/// escape `color`, for `cprintln!`.
+///
+/// The escapes are only written when stdout is a terminal, or when the
+/// `CLICOLOR_FORCE` environment variable is set to anything but `0`.
+pub fn cprintln(color: &str, args: fmt::Arguments) {
+    if escapes_wanted() {
+        stdio::println_args(format_args!("{}{}\x1b[0m", color, args));
+    } else {
+        stdio::println_args(args);
+    }
+}
+
+/// Reads the environment variable `key` and parses its value, for
+/// `env_typed!`.
+pub fn env_typed<T: FromStr>(key: &str) -> Result<T, ParseVarError<T::Err>> {
+    match env::var(key) {
+        Ok(value) => value.parse().map_err(ParseVarError::Parse),
+        Err(e) => Err(ParseVarError::Var(e)),
+    }
+}
+
+/// Reads the environment variable `key` as a list of items separated by
+/// `delim`, for `env_list!`.
+///
+/// The items are trimmed, and empty ones are left out. A variable which
+/// isn't set, or isn't valid unicode, gives an empty list.
+pub fn env_list<P: CharEq>(key: &str, delim: P) -> Vec<String> {
+    match env::var(key) {
+        Ok(value) => {
+            value.split(delim).map(|item| item.trim())
+                 .filter(|item| !item.is_empty())
+                 .map(|item| item.to_string())
+                 .collect()
+        }
+        Err(..) => Vec::new(),
+    }
+}
+
+/// Reads the environment variable `key` as a list, as `env_list` does, and
+/// parses each item, for `env_list!`.
+pub fn env_list_typed<T, P>(key: &str, delim: P) -> Result<Vec<T>, T::Err>
+    where T: FromStr, P: CharEq
+{
+    try_collect(env_list(key, delim).iter().map(|item| item.parse()))
+}
+
+/// Prints `fields` to the task's stderr handle as a JSON-like object, for
+/// `println_json!`.
+pub fn println_json(fields: &[(&str, &fmt::Debug)]) {
+    use fmt::Writer;
+
+    let mut s = String::from_str("{");
+    for (i, &(key, value)) in fields.iter().enumerate() {
+        if i > 0 {
+            s.push_str(", ");
+        }
+        let _ = write!(&mut s, "{:?}: {:?}", key, value);
+    }
+    s.push('}');
+    stdio::eprintln_args(format_args!("{}", s));
+}
+
+/// Prints each of `pairs` on its own line to the task's stderr handle, with
+/// the values lined up in a column after the keys, for `print_kv!`.
+pub fn print_kv(pairs: &[(&fmt::Display, &fmt::Display)]) {
+    let keys = pairs.iter().map(|&(key, _)| format!("{}:", key)).collect::<Vec<_>>();
+    let width = keys.iter().map(|key| key.chars().count()).max().unwrap_or(0);
+    for (key, &(_, value)) in keys.iter().zip(pairs.iter()) {
+        stdio::eprintln_args(format_args!("{:<2$} {}", key, value, width));
+    }
+}
+
+/// Redraws the progress line for `print_progress!`, if stdout is a terminal
+/// (or `CLICOLOR_FORCE` is set).
+pub fn print_progress(current: u64, total: u64) {
+    const WIDTH: u64 = 20;
+
+    if !escapes_wanted() {
+        return;
+    }
+    let current = cmp::min(current, total);
+    // An empty task is finished as soon as it starts.
+    let (filled, percent) = if total == 0 {
+        (WIDTH, 100)
+    } else {
+        // Multiplying in `f64` avoids overflow for huge `current`s.
+        let frac = current as f64 / total as f64;
+        ((frac * WIDTH as f64) as u64, (frac * 100.0) as u64)
+    };
+    let bar = iter::repeat('#').take(filled as usize)
+                  .chain(iter::repeat(' ').take((WIDTH - filled) as usize))
+                  .collect::<String>();
+    stdio::print_args(format_args!("\r[{}] {:>3}% ({}/{})", bar, percent, current, total));
+    stdio::flush();
+}
+
+/// Ends the progress line for `print_progress_done!`.
+pub fn print_progress_done() {
+    if escapes_wanted() {
+        stdio::println("");
+    }
+}
+
+/// Indexing which gives `None` instead of panicking when out of bounds, for
+/// `checked_index!`.
+///
+/// This is implemented for slices, by `usize` indices and by ranges of them.
+pub trait CheckedIndex<Idx> {
+    /// The type of the element or subslice which is indexed.
+    type Output: ?Sized;
+
+    /// Gives the part of `self` at `idx`, if `idx` is in bounds.
+    fn checked_index(&self, idx: Idx) -> Option<&Self::Output>;
+}
+
+impl<T> CheckedIndex<usize> for [T] {
+    type Output = T;
+    fn checked_index(&self, idx: usize) -> Option<&T> { self.get(idx) }
+}
+
+impl<T> CheckedIndex<Range<usize>> for [T] {
+    type Output = [T];
+    fn checked_index(&self, idx: Range<usize>) -> Option<&[T]> {
+        if idx.start <= idx.end && idx.end <= self.len() {
+            Some(&self[idx])
+        } else {
+            None
+        }
+    }
+}
+
+impl<T> CheckedIndex<RangeFrom<usize>> for [T] {
+    type Output = [T];
+    fn checked_index(&self, idx: RangeFrom<usize>) -> Option<&[T]> {
+        self.checked_index(idx.start..self.len())
+    }
+}
+
+impl<T> CheckedIndex<RangeTo<usize>> for [T] {
+    type Output = [T];
+    fn checked_index(&self, idx: RangeTo<usize>) -> Option<&[T]> {
+        self.checked_index(0..idx.end)
+    }
+}
+
+impl<T> CheckedIndex<RangeFull> for [T] {
+    type Output = [T];
+    fn checked_index(&self, _: RangeFull) -> Option<&[T]> { Some(self) }
+}
+
+/// Runs `f` on the current thread, giving its panic message if it panics, for
+/// `capture_panic_message!`.
+pub fn capture_panic_message<T, F>(f: F) -> Result<T, String> where F: FnOnce() -> T {
+    // Catching a panic raised while the thread is already unwinding would
+    // mean unwinding twice, so in that case `f` is just called: a panic from
+    // it aborts, as it would have anyway.
+    if Thread::panicking() {
+        return Ok(f())
+    }
+
+    let mut output = None;
+    let result = {
+        let ptr = &mut output;
+
+        // `try` only catches the panics raised within `f`, and returns normally
+        // either way, so any `try` this thread itself is running under (the
+        // one around every thread's body) is left intact.
+        unsafe {
+            rt::unwind::try(move || *ptr = Some(f()))
+        }
+    };
+    match (output, result) {
+        (Some(value), Ok(())) => Ok(value),
+        (None, Err(payload)) => Err(panic_message(&*payload)),
+        _ => unreachable!(),
+    }
+}
+
+/// Gives the message of a panic with the payload `payload`, recognizing the
+/// same payloads as the message printed when a thread panics.
+fn panic_message(payload: &(Any + Send)) -> String {
+    match payload.downcast_ref::<&'static str>() {
+        Some(s) => s.to_string(),
+        None => match payload.downcast_ref::<String>() {
+            Some(s) => s.clone(),
+            None => match payload.downcast_ref::<backtrace::PanicBacktrace>() {
+                Some(p) => p.msg.clone(),
+                None => "Box<Any>".to_string(),
+            }
+        }
+    }
+}
+
+/// Calls `f` with `guard`, and then drops the guard, for `with_lock!`.
+pub fn with_guard<G, R, F>(mut guard: G, f: F) -> R where F: FnOnce(&mut G) -> R {
+    f(&mut guard)
+}
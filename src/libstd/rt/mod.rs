@@ -30,7 +30,8 @@ use thunk::Thunk;
 
 // Reexport some of our utilities which are expected by other crates.
 pub use self::util::{default_sched_threads, min_stack, running_on_valgrind};
-pub use self::unwind::{begin_unwind, begin_unwind_fmt};
+pub use self::unwind::{begin_unwind, begin_unwind_fmt, begin_unwind_backtrace};
+pub use self::unwind::resume_unwind;
 
 // Reexport some functionality from liballoc.
 pub use alloc::heap;
@@ -47,6 +48,10 @@ pub mod util;
 pub mod unwind;
 pub mod args;
 
+// Support code for the macros exported from std
+#[doc(hidden)]
+pub mod macro_support;
+
 mod at_exit_imp;
 mod libunwind;
 
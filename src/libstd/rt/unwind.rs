@@ -70,6 +70,7 @@ use mem;
 use sync::atomic::{self, Ordering};
 use sync::{Once, ONCE_INIT};
 
+use rt::backtrace;
 use rt::libunwind as uw;
 
 struct Exception {
@@ -523,6 +524,31 @@ pub fn begin_unwind<M: Any + Send>(msg: M, file_line: &(&'static str, uint)) ->
     begin_unwind_inner(box msg, file_line)
 }
 
+/// The entry point for unwinding with a formatted message and a backtrace.
+///
+/// This is the `panic_bt!` counterpart of `begin_unwind_fmt`: the payload is a
+/// `backtrace::PanicBacktrace` holding both the message and a backtrace of
+/// the panicking thread, taken here before any unwinding begins.
+#[inline(never)] #[cold]
+pub fn begin_unwind_backtrace(msg: fmt::Arguments,
+                              file_line: &(&'static str, uint)) -> ! {
+    let payload = backtrace::PanicBacktrace {
+        msg: fmt::format(msg),
+        backtrace: backtrace::capture(),
+    };
+    begin_unwind_inner(box payload, file_line)
+}
+
+/// Starts unwinding again with a payload caught from an earlier panic.
+///
+/// This is used to carry a panic over from one thread to another, as by
+/// `scoped_tasks!`. The payload is passed on as it is, so it can still be
+/// downcast to whatever the original panic was given.
+#[inline(never)] #[cold]
+pub fn resume_unwind(payload: Box<Any + Send>, file_line: &(&'static str, uint)) -> ! {
+    begin_unwind_inner(payload, file_line)
+}
+
 /// The core of the unwinding.
 ///
 /// This is non-generic to avoid instantiation bloat in other crates
@@ -63,7 +63,7 @@ use core::mem;
 use core::ptr;
 use core::usize;
 
-use sync::mpsc::{Receiver, RecvError};
+use sync::mpsc::{Receiver, RecvError, TryRecvError};
 use sync::mpsc::blocking::{self, SignalToken};
 
 /// The "receiver set" of the select interface. This structure is used to manage
@@ -254,6 +254,10 @@ impl<'rx, T: Send> Handle<'rx, T> {
     /// semantics as `Receiver.recv`
     pub fn recv(&mut self) -> Result<T, RecvError> { self.rx.recv() }
 
+    /// Attempt to receive a value on the underlying receiver without blocking.
+    /// This function has the same semantics as `Receiver.try_recv`
+    pub fn try_recv(&mut self) -> Result<T, TryRecvError> { self.rx.try_recv() }
+
     /// Adds this handle to the receiver set that the handle was created from. This
     /// method can be called multiple times, but it has no effect if `add` was
     /// called previously.
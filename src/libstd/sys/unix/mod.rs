@@ -112,6 +112,7 @@ pub fn decode_error(errno: i32) -> IoError {
         libc::EADDRINUSE => (old_io::ConnectionRefused, "address in use"),
         libc::ENOENT => (old_io::FileNotFound, "no such file or directory"),
         libc::EISDIR => (old_io::InvalidInput, "illegal operation on a directory"),
+        libc::EINTR => (old_io::Interrupted, "interrupted system call"),
         libc::ENOSYS => (old_io::IoUnavailable, "function not implemented"),
         libc::EINVAL => (old_io::InvalidInput, "invalid argument"),
         libc::ENOTTY =>
@@ -273,6 +273,26 @@ pub unsafe fn detach(native: rust_thread) {
 }
 
 pub unsafe fn yield_now() { assert_eq!(sched_yield(), 0); }
+
+#[cfg(target_os = "linux")]
+pub unsafe fn set_affinity(cpu: uint) -> bool {
+    // The same size as glibc's cpu_set_t on 64-bit targets; the kernel
+    // accepts masks shorter than its own.
+    let bits = mem::size_of::<libc::c_ulong>() * 8;
+    let mut set = [0 as libc::c_ulong; 16];
+    if cpu >= set.len() * bits {
+        return false;
+    }
+    set[cpu / bits] |= 1 << (cpu % bits);
+    sched_setaffinity(0, mem::size_of_val(&set) as libc::size_t,
+                      set.as_ptr() as *const libc::c_void) == 0
+}
+
+#[cfg(not(target_os = "linux"))]
+pub unsafe fn set_affinity(_cpu: uint) -> bool {
+    false
+}
+
 // glibc >= 2.15 has a __pthread_get_minstack() function that returns
 // PTHREAD_STACK_MIN plus however many bytes are needed for thread-local
 // storage.  We need that information to avoid blowing up when a small stack
@@ -332,6 +352,12 @@ extern {
     fn pthread_setname_np(name: *const libc::c_char) -> libc::c_int;
 }
 
+#[cfg(target_os = "linux")]
+extern {
+    fn sched_setaffinity(pid: libc::pid_t, cpusetsize: libc::size_t,
+                         mask: *const libc::c_void) -> libc::c_int;
+}
+
 #[cfg(target_os = "openbsd")]
 extern {
         pub fn pthread_stackseg_np(thread: libc::pthread_t,
@@ -121,6 +121,7 @@ pub fn decode_error(errno: i32) -> IoError {
         libc::ERROR_OPERATION_ABORTED =>
             (old_io::TimedOut, "operation timed out"),
         libc::WSAEINVAL => (old_io::InvalidInput, "invalid argument"),
+        libc::WSAEINTR => (old_io::Interrupted, "blocking call interrupted"),
         libc::ERROR_CALL_NOT_IMPLEMENTED =>
             (old_io::IoUnavailable, "function not implemented"),
         libc::ERROR_INVALID_HANDLE =>
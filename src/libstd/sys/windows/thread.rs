@@ -88,6 +88,13 @@ pub unsafe fn yield_now() {
     SwitchToThread();
 }
 
+pub unsafe fn set_affinity(cpu: uint) -> bool {
+    if cpu >= mem::size_of::<libc::uintptr_t>() * 8 {
+        return false;
+    }
+    SetThreadAffinityMask(GetCurrentThread(), 1 << cpu) != 0
+}
+
 #[allow(non_snake_case)]
 extern "system" {
     fn CreateThread(lpThreadAttributes: LPSECURITY_ATTRIBUTES,
@@ -98,4 +105,7 @@ extern "system" {
                     lpThreadId: LPDWORD) -> HANDLE;
     fn WaitForSingleObject(hHandle: HANDLE, dwMilliseconds: DWORD) -> DWORD;
     fn SwitchToThread() -> BOOL;
+    fn GetCurrentThread() -> HANDLE;
+    fn SetThreadAffinityMask(hThread: HANDLE,
+                             dwThreadAffinityMask: libc::uintptr_t) -> libc::uintptr_t;
 }
@@ -456,6 +456,30 @@ fn initial_syntax_expander_table(ecfg: &expand::ExpansionConfig) -> SyntaxEnv {
     syntax_expanders.insert(intern("option_env"),
                             builtin_normal_expander(
                                     ext::env::expand_option_env));
+    syntax_expanders.insert(intern("env_parse"),
+                            builtin_normal_expander(
+                                    ext::env::expand_env_parse));
+    syntax_expanders.insert(intern("env_bool"),
+                            builtin_normal_expander(
+                                    ext::env::expand_env_bool));
+    syntax_expanders.insert(intern("const_table"),
+                            builtin_normal_expander(
+                                    ext::const_table::expand_const_table));
+    syntax_expanders.insert(intern("cstr"),
+                            builtin_normal_expander(
+                                    ext::cstr::expand_cstr));
+    syntax_expanders.insert(intern("compile_error"),
+                            builtin_normal_expander(
+                                    ext::compile_error::expand_compile_error));
+    syntax_expanders.insert(intern("reverse_bytes"),
+                            builtin_normal_expander(
+                                    ext::reverse_bytes::expand_reverse_bytes));
+    syntax_expanders.insert(intern("crc32"),
+                            builtin_normal_expander(
+                                    ext::crc32::expand_crc32));
+    syntax_expanders.insert(intern("log2"),
+                            builtin_normal_expander(
+                                    ext::log2::expand_log2));
     syntax_expanders.insert(intern("concat_idents"),
                             builtin_normal_expander(
                                     ext::concat_idents::expand_syntax_ext));
@@ -528,6 +552,9 @@ fn initial_syntax_expander_table(ecfg: &expand::ExpansionConfig) -> SyntaxEnv {
     syntax_expanders.insert(intern("cfg"),
                             builtin_normal_expander(
                                     ext::cfg::expand_cfg));
+    syntax_expanders.insert(intern("debug_only"),
+                            builtin_normal_expander(
+                                    ext::cfg::expand_debug_only));
     syntax_expanders.insert(intern("trace_macros"),
                             builtin_normal_expander(
                                     ext::trace_macros::expand_trace_macros));
@@ -37,3 +37,28 @@ pub fn expand_cfg<'cx>(cx: &mut ExtCtxt,
     let matches_cfg = attr::cfg_matches(&cx.parse_sess.span_diagnostic, &cx.cfg, &*cfg);
     MacExpr::new(cx.expr_bool(sp, matches_cfg))
 }
+
+/// Expands `debug_only!({ ... })` to the given block, or to `()` when the
+/// crate is being compiled with `--cfg ndebug`.
+///
+/// The block is dropped before anything else looks at it, so in a release
+/// build its contents are never resolved or type checked.
+pub fn expand_debug_only<'cx>(cx: &mut ExtCtxt,
+                              sp: Span,
+                              tts: &[ast::TokenTree])
+                              -> Box<base::MacResult+'static> {
+    let mut p = cx.new_parser_from_tts(tts);
+    let block = p.parse_block();
+
+    if !p.eat(&token::Eof) {
+        cx.span_err(sp, "expected a single block");
+        return DummyResult::expr(sp);
+    }
+
+    if attr::contains_name(&cx.cfg[], "ndebug") {
+        return MacExpr::new(cx.expr_tuple(sp, vec![]));
+    }
+    // The block is used as a statement so that the expansion is always `()`.
+    let stmt = cx.stmt_expr(cx.expr_block(block));
+    MacExpr::new(cx.expr_block(cx.block(sp, vec![stmt], None)))
+}
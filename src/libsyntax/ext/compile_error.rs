@@ -0,0 +1,27 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+/*
+ * The compiler code necessary to support the compile_error! extension, which
+ * reports a custom error message at the point where it is expanded.
+ */
+
+use ast;
+use codemap::Span;
+use ext::base::*;
+use ext::base;
+
+pub fn expand_compile_error<'cx>(cx: &'cx mut ExtCtxt, sp: Span, tts: &[ast::TokenTree])
+                                 -> Box<base::MacResult+'cx> {
+    if let Some(msg) = get_single_str_from_tts(cx, sp, tts, "compile_error!") {
+        cx.span_err(sp, &msg[]);
+    }
+    DummyResult::any(sp)
+}
@@ -0,0 +1,111 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+/*
+ * The compiler code necessary to support the const_table! extension, which
+ * expands `const_table!(default; 0 => a, 3 => b)` into the array expression
+ * `[a, default, default, b]`.
+ */
+
+use ast;
+use codemap::Span;
+use ext::base::*;
+use ext::base;
+use ext::build::AstBuilder;
+use parse::token;
+use ptr::P;
+
+/// The largest table `const_table!` will build. Anything bigger is almost
+/// certainly a typo in one of the indices.
+const MAX_TABLE_LEN: u64 = 4096;
+
+pub fn expand_const_table<'cx>(cx: &'cx mut ExtCtxt, sp: Span, tts: &[ast::TokenTree])
+                               -> Box<base::MacResult+'cx> {
+    let mut p = cx.new_parser_from_tts(tts);
+    if p.token == token::Eof {
+        cx.span_err(sp, "const_table! takes a default value and a list of \
+                         `index => value` entries");
+        return DummyResult::expr(sp);
+    }
+    let default = cx.expander().fold_expr(p.parse_expr());
+    p.expect(&token::Semi);
+
+    let mut entries = Vec::new();
+    while p.token != token::Eof {
+        let index = cx.expander().fold_expr(p.parse_expr());
+        p.expect(&token::FatArrow);
+        let value = cx.expander().fold_expr(p.parse_expr());
+        entries.push((index, value));
+        if !p.eat(&token::Comma) {
+            break
+        }
+    }
+    if p.token != token::Eof {
+        cx.span_err(p.span, "expected `,` or the end of the const_table! entries");
+        return DummyResult::expr(sp);
+    }
+    if entries.is_empty() {
+        cx.span_err(sp, "const_table! requires at least one `index => value` entry");
+        return DummyResult::expr(sp);
+    }
+
+    let mut slots: Vec<Option<(P<ast::Expr>, Span)>> = Vec::new();
+    let mut ok = true;
+    for (index, value) in entries {
+        let i = match index.node {
+            ast::ExprLit(ref lit) => match lit.node {
+                ast::LitInt(i, ast::UnsignedIntLit(_)) |
+                ast::LitInt(i, ast::SignedIntLit(_, ast::Plus)) |
+                ast::LitInt(i, ast::UnsuffixedIntLit(ast::Plus)) => Some(i),
+                _ => None,
+            },
+            _ => None,
+        };
+        let i = match i {
+            Some(i) => i,
+            None => {
+                cx.span_err(index.span, "const_table! indices must be integer literals");
+                ok = false;
+                continue
+            }
+        };
+        if i >= MAX_TABLE_LEN {
+            cx.span_err(index.span,
+                        &format!("const_table! index {} is too large; tables may have \
+                                  at most {} entries", i, MAX_TABLE_LEN)[]);
+            ok = false;
+            continue
+        }
+
+        let i = i as usize;
+        while slots.len() <= i {
+            slots.push(None);
+        }
+        if let Some((_, first)) = slots[i] {
+            cx.span_err(index.span,
+                        &format!("const_table! index {} is given more than once", i)[]);
+            cx.span_note(first, "first given here");
+            ok = false;
+            continue
+        }
+        slots[i] = Some((value, index.span));
+    }
+    if !ok {
+        return DummyResult::expr(sp);
+    }
+
+    let exprs = slots.into_iter().map(|slot| {
+        match slot {
+            Some((value, _)) => value,
+            None => default.clone(),
+        }
+    }).collect();
+    MacExpr::new(cx.expr_vec(sp, exprs))
+}
@@ -0,0 +1,67 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+/*
+ * The compiler code necessary to support the crc32! extension, which computes
+ * the CRC-32 checksum of a byte string literal and expands to it as a `u32`
+ * literal.
+ */
+
+use ast;
+use codemap::Span;
+use ext::base::*;
+use ext::base;
+use ext::build::AstBuilder;
+use parse::token;
+
+/// The CRC-32 polynomial used by zlib, PNG and Ethernet, in its bit-reversed
+/// form.
+const POLYNOMIAL: u32 = 0xedb88320;
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &b in bytes.iter() {
+        crc ^= b as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 { (crc >> 1) ^ POLYNOMIAL } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+pub fn expand_crc32<'cx>(cx: &'cx mut ExtCtxt, sp: Span, tts: &[ast::TokenTree])
+                         -> Box<base::MacResult+'cx> {
+    let mut p = cx.new_parser_from_tts(tts);
+    if p.token == token::Eof {
+        cx.span_err(sp, "crc32! takes 1 argument");
+        return DummyResult::expr(sp);
+    }
+    let e = cx.expander().fold_expr(p.parse_expr());
+    if p.token != token::Eof {
+        cx.span_err(sp, "crc32! takes 1 argument");
+        return DummyResult::expr(sp);
+    }
+
+    let crc = match e.node {
+        ast::ExprLit(ref lit) => match lit.node {
+            ast::LitBinary(ref bytes) => crc32(&bytes[]),
+            _ => {
+                cx.span_err(lit.span, "argument must be a byte string literal");
+                return DummyResult::expr(sp);
+            }
+        },
+        _ => {
+            cx.span_err(e.span, "argument must be a byte string literal");
+            return DummyResult::expr(sp);
+        }
+    };
+
+    MacExpr::new(cx.expr_lit(sp, ast::LitInt(crc as u64, ast::UnsignedIntLit(ast::TyU32))))
+}
@@ -0,0 +1,39 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+/*
+ * The compiler code necessary to support the cstr! extension, which turns a
+ * string literal into a NUL-terminated byte string literal.
+ */
+
+use ast;
+use codemap::Span;
+use ext::base::*;
+use ext::base;
+use ext::build::AstBuilder;
+
+use std::rc::Rc;
+
+pub fn expand_cstr<'cx>(cx: &'cx mut ExtCtxt, sp: Span, tts: &[ast::TokenTree])
+                        -> Box<base::MacResult+'cx> {
+    let s = match get_single_str_from_tts(cx, sp, tts, "cstr!") {
+        Some(s) => s,
+        None => return DummyResult::expr(sp)
+    };
+    if let Some(i) = s.as_bytes().iter().position(|&b| b == 0) {
+        cx.span_err(sp, &format!("cstr! literal contains an interior NUL byte \
+                                  at byte {}", i)[]);
+        return DummyResult::expr(sp);
+    }
+
+    let mut bytes = s.into_bytes();
+    bytes.push(0);
+    MacExpr::new(cx.expr_lit(sp, ast::LitBinary(Rc::new(bytes))))
+}
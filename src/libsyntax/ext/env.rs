@@ -20,8 +20,10 @@ use ext::base::*;
 use ext::base;
 use ext::build::AstBuilder;
 use parse::token;
+use ptr::P;
 
 use std::env;
+use std::num::Float;
 
 pub fn expand_option_env<'cx>(cx: &'cx mut ExtCtxt, sp: Span, tts: &[ast::TokenTree])
                               -> Box<base::MacResult+'cx> {
@@ -110,3 +112,134 @@ pub fn expand_env<'cx>(cx: &'cx mut ExtCtxt, sp: Span, tts: &[ast::TokenTree])
     };
     MacExpr::new(e)
 }
+
+pub fn expand_env_parse<'cx>(cx: &'cx mut ExtCtxt, sp: Span, tts: &[ast::TokenTree])
+                             -> Box<base::MacResult+'cx> {
+    let mut p = cx.new_parser_from_tts(tts);
+    if p.token == token::Eof {
+        cx.span_err(sp, "env_parse! takes a variable name and a type");
+        return DummyResult::expr(sp);
+    }
+    let name = cx.expander().fold_expr(p.parse_expr());
+    p.expect(&token::Colon);
+    let ty = p.parse_ty();
+    let default = if p.eat(&token::Eq) {
+        Some(cx.expander().fold_expr(p.parse_expr()))
+    } else {
+        None
+    };
+    if p.token != token::Eof {
+        cx.span_err(sp, "expected `=` or the end of the env_parse! arguments");
+        return DummyResult::expr(sp);
+    }
+
+    let var = match expr_to_string(cx, name, "expected string literal") {
+        None => return DummyResult::expr(sp),
+        Some((v, _style)) => v
+    };
+    let ty_name = match ty.node {
+        ast::TyPath(ref path, _) if path.segments.len() == 1 => {
+            token::get_ident(path.segments[0].identifier)
+        }
+        _ => token::intern_and_get_ident(""),
+    };
+    if !ENV_PARSE_TYPES.contains(&&ty_name[]) {
+        cx.span_err(ty.span, "env_parse! can only produce integer, float, `bool` \
+                              and `char` values");
+        return DummyResult::expr(sp);
+    }
+
+    let value = match (env::var(&var[]), default) {
+        (Ok(value), _) => value,
+        (Err(_), Some(default)) => return MacExpr::new(default),
+        (Err(_), None) => {
+            cx.span_err(sp, &format!("environment variable `{}` not defined", var)[]);
+            return DummyResult::expr(sp);
+        }
+    };
+
+    match parse_env_value(cx, sp, &ty_name[], &value[]) {
+        Some(e) => MacExpr::new(e),
+        None => {
+            cx.span_err(sp, &format!("environment variable `{}` has the value `{}`, \
+                                      which cannot be parsed as `{}`",
+                                     var, value, ty_name)[]);
+            DummyResult::expr(sp)
+        }
+    }
+}
+
+pub fn expand_env_bool<'cx>(cx: &'cx mut ExtCtxt, sp: Span, tts: &[ast::TokenTree])
+                            -> Box<base::MacResult+'cx> {
+    let var = match get_single_str_from_tts(cx, sp, tts, "env_bool!") {
+        None => return DummyResult::expr(sp),
+        Some(v) => v
+    };
+
+    let enabled = match env::var(&var[]) {
+        Ok(ref value) => *value == "1" || *value == "true",
+        Err(..) => false,
+    };
+    MacExpr::new(cx.expr_bool(sp, enabled))
+}
+
+/// The types which `env_parse!` knows how to produce a literal for.
+static ENV_PARSE_TYPES: &'static [&'static str] = &[
+    "i8", "i16", "i32", "i64", "isize",
+    "u8", "u16", "u32", "u64", "usize",
+    "f32", "f64", "bool", "char",
+];
+
+/// Parses `value` into a literal of the primitive type named `ty`, returning
+/// `None` if the value is not valid for that type.
+fn parse_env_value(cx: &ExtCtxt, sp: Span, ty: &str, value: &str) -> Option<P<ast::Expr>> {
+    // Negative numbers are produced as a negated literal, just as the parser
+    // would have produced them.
+    let (negative, digits) = if value.starts_with("-") {
+        (true, &value[1..])
+    } else if value.starts_with("+") {
+        (false, &value[1..])
+    } else {
+        (false, value)
+    };
+    let negate = |lit: P<ast::Expr>| {
+        if negative { cx.expr_unary(sp, ast::UnNeg, lit) } else { lit }
+    };
+
+    let signed = |v: Option<i64>, ty: ast::IntTy| {
+        v.map(|v| {
+            // avoid overflow when negating the minimum value
+            let abs = if v < 0 { (-(v + 1)) as u64 + 1 } else { v as u64 };
+            negate(cx.expr_lit(sp, ast::LitInt(abs, ast::SignedIntLit(ty, ast::Plus))))
+        })
+    };
+    let unsigned = |v: Option<u64>, ty: ast::UintTy| {
+        v.map(|v| cx.expr_lit(sp, ast::LitInt(v, ast::UnsignedIntLit(ty))))
+    };
+    let float = |v: Option<f64>, ty: ast::FloatTy| {
+        // The digits are used verbatim, rather than reformatting the parsed
+        // value, so that no precision is lost.
+        v.and_then(|v| if v.is_finite() { Some(v) } else { None }).map(|_| {
+            let s = token::intern_and_get_ident(digits);
+            negate(cx.expr_lit(sp, ast::LitFloat(s, ty)))
+        })
+    };
+
+    match ty {
+        "i8" => signed(value.parse::<i8>().ok().map(|v| v as i64), ast::TyI8),
+        "i16" => signed(value.parse::<i16>().ok().map(|v| v as i64), ast::TyI16),
+        "i32" => signed(value.parse::<i32>().ok().map(|v| v as i64), ast::TyI32),
+        "i64" => signed(value.parse::<i64>().ok(), ast::TyI64),
+        "isize" => signed(value.parse::<isize>().ok().map(|v| v as i64), ast::TyIs(false)),
+        "u8" => unsigned(value.parse::<u8>().ok().map(|v| v as u64), ast::TyU8),
+        "u16" => unsigned(value.parse::<u16>().ok().map(|v| v as u64), ast::TyU16),
+        "u32" => unsigned(value.parse::<u32>().ok().map(|v| v as u64), ast::TyU32),
+        "u64" => unsigned(value.parse::<u64>().ok(), ast::TyU64),
+        "usize" => unsigned(value.parse::<usize>().ok().map(|v| v as u64), ast::TyUs(false)),
+        "f32" => float(value.parse::<f32>().ok().map(|v| v as f64), ast::TyF32),
+        "f64" => float(value.parse::<f64>().ok(), ast::TyF64),
+        "bool" => value.parse::<bool>().ok().map(|b| cx.expr_bool(sp, b)),
+        "char" => value.parse::<char>().ok().map(|c| cx.expr_lit(sp, ast::LitChar(c))),
+        _ => None,
+    }
+}
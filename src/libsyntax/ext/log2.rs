@@ -0,0 +1,65 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+/*
+ * The compiler code necessary to support the log2! extension, which computes
+ * the floor of the base-2 logarithm of an integer literal and expands to it
+ * as an unsuffixed integer literal.
+ */
+
+use ast;
+use codemap::Span;
+use ext::base::*;
+use ext::base;
+use ext::build::AstBuilder;
+use parse::token;
+
+use std::num::Int;
+
+pub fn expand_log2<'cx>(cx: &'cx mut ExtCtxt, sp: Span, tts: &[ast::TokenTree])
+                        -> Box<base::MacResult+'cx> {
+    let mut p = cx.new_parser_from_tts(tts);
+    if p.token == token::Eof {
+        cx.span_err(sp, "log2! takes 1 argument");
+        return DummyResult::expr(sp);
+    }
+    let e = cx.expander().fold_expr(p.parse_expr());
+    if p.token != token::Eof {
+        cx.span_err(sp, "log2! takes 1 argument");
+        return DummyResult::expr(sp);
+    }
+
+    let n = match e.node {
+        ast::ExprLit(ref lit) => match lit.node {
+            ast::LitInt(_, ast::SignedIntLit(_, ast::Minus)) |
+            ast::LitInt(_, ast::UnsuffixedIntLit(ast::Minus)) => {
+                cx.span_err(lit.span, "argument must be a positive integer literal");
+                return DummyResult::expr(sp);
+            }
+            ast::LitInt(0, _) => {
+                cx.span_err(lit.span, "the logarithm of zero is undefined");
+                return DummyResult::expr(sp);
+            }
+            ast::LitInt(n, _) => n,
+            _ => {
+                cx.span_err(lit.span, "argument must be an integer literal");
+                return DummyResult::expr(sp);
+            }
+        },
+        _ => {
+            cx.span_err(e.span, "argument must be an integer literal");
+            return DummyResult::expr(sp);
+        }
+    };
+
+    // The index of the highest set bit.
+    let log = 63 - n.leading_zeros() as u64;
+    MacExpr::new(cx.expr_lit(sp, ast::LitInt(log, ast::UnsuffixedIntLit(ast::Plus))))
+}
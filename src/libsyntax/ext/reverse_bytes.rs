@@ -0,0 +1,52 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+/*
+ * The compiler code necessary to support the reverse_bytes! extension, which
+ * turns a byte string literal into an array of its bytes in reverse order.
+ */
+
+use ast;
+use codemap::Span;
+use ext::base::*;
+use ext::base;
+use ext::build::AstBuilder;
+use parse::token;
+
+pub fn expand_reverse_bytes<'cx>(cx: &'cx mut ExtCtxt, sp: Span, tts: &[ast::TokenTree])
+                                 -> Box<base::MacResult+'cx> {
+    let mut p = cx.new_parser_from_tts(tts);
+    if p.token == token::Eof {
+        cx.span_err(sp, "reverse_bytes! takes 1 argument");
+        return DummyResult::expr(sp);
+    }
+    let e = cx.expander().fold_expr(p.parse_expr());
+    if p.token != token::Eof {
+        cx.span_err(sp, "reverse_bytes! takes 1 argument");
+        return DummyResult::expr(sp);
+    }
+
+    let bytes = match e.node {
+        ast::ExprLit(ref lit) => match lit.node {
+            ast::LitBinary(ref bytes) => bytes.clone(),
+            _ => {
+                cx.span_err(lit.span, "argument must be a byte string literal");
+                return DummyResult::expr(sp);
+            }
+        },
+        _ => {
+            cx.span_err(e.span, "argument must be a byte string literal");
+            return DummyResult::expr(sp);
+        }
+    };
+
+    let exprs = bytes.iter().rev().map(|&b| cx.expr_u8(sp, b)).collect();
+    MacExpr::new(cx.expr_vec(sp, exprs))
+}
@@ -97,15 +97,21 @@ pub mod ext {
     pub mod base;
     pub mod build;
     pub mod cfg;
+    pub mod compile_error;
     pub mod concat;
     pub mod concat_idents;
+    pub mod const_table;
+    pub mod crc32;
+    pub mod cstr;
     pub mod deriving;
     pub mod env;
     pub mod expand;
     pub mod format;
+    pub mod log2;
     pub mod log_syntax;
     pub mod mtwt;
     pub mod quote;
+    pub mod reverse_bytes;
     pub mod source_util;
     pub mod trace_macros;
 
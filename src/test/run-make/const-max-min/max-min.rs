@@ -0,0 +1,40 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+const A: usize = 12;
+const B: usize = 40;
+
+static LARGER: [u8; const_max!(A, B)] = [0; const_max!(A, B)];
+static SMALLER: [u8; const_min!(A, B)] = [0; const_min!(A, B)];
+static LARGEST: [u8; const_max!(A, B, 7, 100, 3)] = [0; const_max!(A, B, 7, 100, 3)];
+
+const EQUAL: usize = const_max!(A, A);
+const SIGNED_MAX: i32 = const_max!(-5, -20);
+const SIGNED_MIN: i32 = const_min!(-5, -20, 3);
+const WIDE: u64 = const_max!(0, 0xffff_ffff_ffff);
+const BYTE: u8 = const_min!(255, 0, 17);
+
+fn main() {
+    assert_eq!(LARGER.len(), 40);
+    assert_eq!(SMALLER.len(), 12);
+    assert_eq!(LARGEST.len(), 100);
+
+    let local = [0u32; const_min!(B, A, 30)];
+    assert_eq!(local.len(), 12);
+
+    assert_eq!(EQUAL, 12);
+    assert_eq!(SIGNED_MAX, -5);
+    assert_eq!(SIGNED_MIN, -20);
+    assert_eq!(WIDE, 0xffff_ffff_ffff);
+    assert_eq!(BYTE, 0);
+
+    assert_eq!(const_max!(3u8, 200), 200);
+    assert_eq!(const_min!(200u8, 3), 3);
+}
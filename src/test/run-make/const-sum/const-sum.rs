@@ -0,0 +1,32 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+const HEADER: usize = 4;
+
+static ONE: [u8; const_sum!(7)] = [0; const_sum!(7)];
+static FOUR: [u8; const_sum!(1, 2, 3, 4)] = [0; const_sum!(1, 2, 3, 4)];
+static MIXED: [u8; const_sum!(HEADER, 2 * 3, 1 << 4,)] = [0; 26];
+
+const SIGNED: i32 = const_sum!(10, -3, -20);
+const WIDE: u64 = const_sum!(0xffff_ffff, 1);
+const NESTED: usize = const_sum!(const_sum!(1, 2), const_sum!(3, 4), 5);
+
+fn main() {
+    assert_eq!(ONE.len(), 7);
+    assert_eq!(FOUR.len(), 10);
+    assert_eq!(MIXED.len(), 26);
+
+    let local = [0u32; const_sum!(HEADER, HEADER)];
+    assert_eq!(local.len(), 8);
+
+    assert_eq!(SIGNED, -13);
+    assert_eq!(WIDE, 0x1_0000_0000);
+    assert_eq!(NESTED, 15);
+}
@@ -0,0 +1,40 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[derive(Copy)]
+enum Color { Red = 0, Green = 2, Blue = 5 }
+
+static CODES: [u8; 6] = const_table!(0xff;
+    2 => b'g',
+    0 => b'r',
+    5 => b'b',
+);
+
+const PRIMES: [bool; 8] = const_table!(false; 2 => true, 3 => true, 5 => true, 7 => true);
+
+fn code(c: Color) -> u8 {
+    CODES[c as usize]
+}
+
+fn main() {
+    assert_eq!(CODES.len(), 6);
+    assert_eq!(code(Color::Red), b'r');
+    assert_eq!(code(Color::Green), b'g');
+    assert_eq!(code(Color::Blue), b'b');
+    assert_eq!(CODES[1], 0xff);
+    assert_eq!(CODES[3], 0xff);
+    assert_eq!(CODES[4], 0xff);
+
+    let primes = (0..PRIMES.len()).filter(|&i| PRIMES[i]).collect::<Vec<_>>();
+    assert_eq!(primes, vec![2, 3, 5, 7]);
+
+    let single = const_table!(0i32; 0 => 9);
+    assert_eq!(single, [9]);
+}
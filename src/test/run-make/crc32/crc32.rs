@@ -0,0 +1,27 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+const EMPTY: u32 = crc32!(b"");
+static CHECK: u32 = crc32!(b"123456789");
+const TABLE: [u32; 2] = [crc32!(b"a"), crc32!(b"\x00\xff")];
+
+fn main() {
+    assert_eq!(EMPTY, 0);
+    assert_eq!(CHECK, 0xcbf43926);
+    assert_eq!(TABLE, [0xe8b7be43, 0x6cdbfd72]);
+    assert_eq!(crc32!(b"The quick brown fox jumps over the lazy dog"), 0x414fa339);
+
+    // usable wherever a constant is needed
+    let n: u32 = 0x414fa339;
+    match n {
+        crc32!(b"The quick brown fox jumps over the lazy dog") => {}
+        _ => panic!("no match"),
+    }
+}
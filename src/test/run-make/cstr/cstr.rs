@@ -0,0 +1,34 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(libc)]
+
+extern crate libc;
+
+use libc::c_char;
+
+extern {
+    fn strlen(s: *const c_char) -> libc::size_t;
+}
+
+static GREETING: &'static [u8] = cstr!("hello");
+
+fn main() {
+    assert_eq!(GREETING, b"hello\0");
+    assert_eq!(GREETING.len(), 6);
+    assert_eq!(unsafe { strlen(GREETING.as_ptr() as *const c_char) }, 5);
+
+    let empty = cstr!("");
+    assert_eq!(empty, b"\0");
+    assert_eq!(unsafe { strlen(empty.as_ptr() as *const c_char) }, 0);
+
+    // multi-byte characters are kept as UTF-8
+    assert_eq!(cstr!("né"), b"n\xc3\xa9\0");
+}
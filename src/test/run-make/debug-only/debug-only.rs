@@ -0,0 +1,35 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Only exists in debug builds; referencing it from release code would not
+// compile.
+#[cfg(not(ndebug))]
+fn check_invariants(v: &[i32]) -> bool {
+    v.iter().all(|&x| x >= 0)
+}
+
+fn main() {
+    let v = vec![1, 2, 3];
+    let mut checked = 0;
+
+    let unit: () = debug_only!({
+        assert!(check_invariants(&v[]));
+        checked += 1;
+    });
+    assert_eq!(unit, ());
+
+    debug_only!({ checked += 10; });
+
+    if cfg!(ndebug) {
+        assert_eq!(checked, 0);
+    } else {
+        assert_eq!(checked, 11);
+    }
+}
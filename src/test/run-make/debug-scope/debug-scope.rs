@@ -0,0 +1,59 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![allow(unknown_features)]
+#![feature(box_syntax)]
+
+use std::old_io::{ChanReader, ChanWriter, stdio};
+use std::sync::mpsc::channel;
+use std::thread::Thread;
+
+fn main() {
+    let (tx, rx) = channel();
+    let (mut r, w) = (ChanReader::new(rx), ChanWriter::new(tx));
+    let _t = Thread::spawn(move|| {
+        stdio::set_stderr(box w);
+        let n = debug_scope!("outer", {
+            let a = debug_scope!("first", { 1 });
+            let b = debug_scope!("second", {
+                debug_scope!("deepest", { 2 })
+            });
+            a + b
+        });
+        assert_eq!(n, 3);
+
+        debug_scope!("unwound", {
+            debug_scope!("panicking", { panic!("leaving the scopes") })
+        });
+    });
+
+    let out = r.read_to_string().unwrap();
+    let lines = out.lines()
+                   .filter(|l| l.trim_left().starts_with("<") || l.trim_left().starts_with(">"))
+                   .collect::<Vec<_>>();
+    if cfg!(ndebug) {
+        assert!(lines.is_empty(), "{}", out);
+    } else {
+        assert_eq!(lines, vec![
+            "> outer",
+            "  > first",
+            "  < first",
+            "  > second",
+            "    > deepest",
+            "    < deepest",
+            "  < second",
+            "< outer",
+            "> unwound",
+            "  > panicking",
+            "  < panicking",
+            "< unwound",
+        ]);
+    }
+}
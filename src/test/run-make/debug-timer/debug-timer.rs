@@ -0,0 +1,44 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![allow(unknown_features)]
+#![feature(box_syntax)]
+
+use std::old_io::{ChanReader, ChanWriter, stdio, timer};
+use std::sync::mpsc::channel;
+use std::thread::Thread;
+use std::time::Duration;
+
+fn main() {
+    let (tx, rx) = channel();
+    let (mut r, w) = (ChanReader::new(rx), ChanWriter::new(tx));
+    let _t = Thread::spawn(move|| {
+        stdio::set_stderr(box w);
+        {
+            debug_timer!("sleep");
+            timer::sleep(Duration::milliseconds(20));
+        }
+        debug_timer!("unwound");
+        panic!("leaving the scope");
+    });
+
+    let out = r.read_to_string().unwrap();
+    let lines = out.lines().collect::<Vec<_>>();
+    if cfg!(ndebug) {
+        assert!(!lines.iter().any(|l| l.starts_with("sleep") || l.starts_with("unwound")));
+    } else {
+        // the sleep is at least 20ms, which is formatted in seconds
+        let sleep = lines.iter().find(|l| l.starts_with("sleep: ")).unwrap();
+        let secs = sleep["sleep: ".len()..sleep.len() - 1].parse::<f64>().unwrap();
+        assert!(sleep.ends_with("s") && secs >= 0.02 && secs < 60.0, "{}", sleep);
+
+        assert!(lines.iter().any(|l| l.starts_with("unwound: ")), "{}", out);
+    }
+}
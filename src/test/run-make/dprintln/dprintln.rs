@@ -0,0 +1,56 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![allow(unknown_features)]
+#![feature(box_syntax)]
+
+use std::cell::Cell;
+use std::old_io::{ChanReader, ChanWriter, stdio};
+use std::sync::mpsc::channel;
+use std::thread::Thread;
+
+// Only exists in debug builds; mentioning it in release code would not
+// compile.
+#[cfg(not(ndebug))]
+fn debug_name() -> &'static str { "debug" }
+
+fn bump(c: &Cell<u32>) -> u32 {
+    c.set(c.get() + 1);
+    c.get()
+}
+
+fn main() {
+    let (tx, rx) = channel();
+    let (mut r, w) = (ChanReader::new(rx), ChanWriter::new(tx));
+    let _t = Thread::spawn(move|| {
+        stdio::set_stderr(box w);
+        let evaluated = Cell::new(0);
+        dprintln!("count {}", bump(&evaluated));
+        dprintln!("name {} {}", debug_name(), bump(&evaluated),);
+        dprintln!("plain");
+        if cfg!(ndebug) {
+            assert_eq!(evaluated.get(), 0);
+        } else {
+            assert_eq!(evaluated.get(), 2);
+        }
+    });
+
+    let out = r.read_to_string().unwrap();
+    if cfg!(ndebug) {
+        assert_eq!(out, "");
+    } else {
+        let lines = out.lines().collect::<Vec<_>>();
+        assert_eq!(lines, vec![
+            "[dprintln.rs:35] count 1",
+            "[dprintln.rs:36] name debug 2",
+            "[dprintln.rs:37] plain",
+        ]);
+    }
+}
@@ -0,0 +1,18 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+const FEATURE: bool = env_bool!("ENV_BOOL_FEATURE");
+
+fn main() {
+    assert_eq!(FEATURE, cfg!(expect_enabled));
+
+    let mode = if env_bool!("ENV_BOOL_FEATURE") { "on" } else { "off" };
+    assert_eq!(mode, if cfg!(expect_enabled) { "on" } else { "off" });
+}
@@ -0,0 +1,18 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+const MAX_CONN: u32 = env_parse!("ENV_PARSE_NOT_DEFINED": u32 = 16);
+
+static SLOTS: [u8; env_parse!("ENV_PARSE_NOT_DEFINED": usize = 4)] = [0; 4];
+
+fn main() {
+    assert_eq!(MAX_CONN, 16);
+    assert_eq!(SLOTS.len(), 4);
+}
@@ -0,0 +1,23 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+const MAX_CONN: u32 = env_parse!("ENV_PARSE_MAX_CONN": u32);
+const OFFSET: i32 = env_parse!("ENV_PARSE_OFFSET": i32);
+const RATIO: f64 = env_parse!("ENV_PARSE_RATIO": f64);
+
+static SLOTS: [u8; env_parse!("ENV_PARSE_MAX_CONN": usize)] =
+    [0; env_parse!("ENV_PARSE_MAX_CONN": usize)];
+
+fn main() {
+    assert_eq!(MAX_CONN, 32);
+    assert_eq!(OFFSET, -7);
+    assert_eq!(RATIO, 0.25);
+    assert_eq!(SLOTS.len(), 32);
+}
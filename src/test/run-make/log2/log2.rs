@@ -0,0 +1,38 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+const ONE: u32 = log2!(1);
+static KILO: usize = log2!(1024);
+const TABLE: [u8; log2!(16)] = [0; 4];
+
+fn main() {
+    assert_eq!(ONE, 0);
+    assert_eq!(KILO, 10);
+    assert_eq!(TABLE.len(), 4);
+
+    // powers of two
+    assert_eq!(log2!(2), 1);
+    assert_eq!(log2!(256u16), 8);
+    assert_eq!(log2!(0x8000_0000_0000_0000u64), 63);
+
+    // everything else is rounded down
+    assert_eq!(log2!(3), 1);
+    assert_eq!(log2!(1000), 9);
+    assert_eq!(log2!(1023), 9);
+    assert_eq!(log2!(1025), 10);
+    assert_eq!(log2!(0xffff_ffff_ffff_ffffu64), 63);
+
+    // usable wherever a constant is needed
+    let n = 6;
+    match n {
+        log2!(64) => {}
+        _ => panic!("no match"),
+    }
+}
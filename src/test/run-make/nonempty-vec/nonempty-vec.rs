@@ -0,0 +1,22 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+fn main() {
+    let one = nonempty_vec![1];
+    assert_eq!(one, vec![1]);
+    assert_eq!(one.capacity(), 1);
+
+    let several = nonempty_vec!["a", "b", "c",];
+    assert_eq!(several, vec!["a", "b", "c"]);
+    assert_eq!(several.capacity(), 3);
+
+    let computed = nonempty_vec![1 + 1, 2 * 3];
+    assert_eq!(computed, vec![2, 6]);
+}
@@ -0,0 +1,53 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// The fields are only ever looked at through offset_of!
+#![allow(dead_code)]
+
+#[repr(C)]
+struct Inner {
+    x: u8,
+    y: u32,
+}
+
+#[repr(C)]
+struct Outer {
+    a: u8,
+    b: u16,
+    inner: Inner,
+    c: u32,
+    d: [u8; 3],
+    e: u16,
+}
+
+#[repr(C)]
+struct Packed3 {
+    a: u8,
+    b: u8,
+    c: u8,
+}
+
+fn main() {
+    assert_eq!(offset_of!(Inner, x), 0);
+    assert_eq!(offset_of!(Inner, y), 4);
+
+    assert_eq!(offset_of!(Outer, a), 0);
+    assert_eq!(offset_of!(Outer, b), 2);
+    assert_eq!(offset_of!(Outer, inner), 4);
+    assert_eq!(offset_of!(Outer, c), 12);
+    assert_eq!(offset_of!(Outer, d), 16);
+    assert_eq!(offset_of!(Outer, e), 20);
+
+    // nested fields are relative to the outer struct
+    assert_eq!(offset_of!(Outer, inner.x), 4);
+    assert_eq!(offset_of!(Outer, inner.y), 8);
+
+    assert_eq!(offset_of!(Packed3, c), 2);
+}
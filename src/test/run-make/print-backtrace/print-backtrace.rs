@@ -0,0 +1,46 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![allow(unknown_features)]
+#![feature(box_syntax)]
+
+use std::old_io::{ChanReader, ChanWriter, stdio};
+use std::sync::mpsc::channel;
+use std::thread::Thread;
+
+fn traced() -> u32 {
+    print_backtrace!();
+    1
+}
+
+fn main() {
+    let (tx, rx) = channel();
+    let (mut r, w) = (ChanReader::new(rx), ChanWriter::new(tx));
+    let _t = Thread::spawn(move|| {
+        stdio::set_stderr(box w);
+        // the thread carries on afterwards
+        assert_eq!(traced(), 1);
+        print_backtrace!(force);
+    });
+
+    let out = r.read_to_string().unwrap();
+    let reports = out.lines()
+                     .filter(|l| l.ends_with(": stack backtrace:") ||
+                                 l.ends_with(": backtrace unavailable"))
+                     .collect::<Vec<_>>();
+    let expected = if cfg!(ndebug) { 1 } else { 2 };
+    assert_eq!(reports.len(), expected);
+    for line in reports.iter() {
+        assert!(line.contains("print-backtrace.rs:"), "{}", out);
+    }
+    if !cfg!(ndebug) {
+        assert!(reports[0].contains("print-backtrace.rs:19:"), "{}", out);
+    }
+}
@@ -0,0 +1,31 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+const ABC: [u8; 3] = reverse_bytes!(b"abc");
+static ESCAPES: [u8; 4] = reverse_bytes!(b"\x00\x01\xfe\xff");
+const ONE: [u8; 1] = reverse_bytes!(b"z");
+const NONE: [u8; 0] = reverse_bytes!(b"");
+
+fn main() {
+    assert_eq!(ABC, [b'c', b'b', b'a']);
+    assert_eq!(&ABC[], &b"cba"[]);
+    assert_eq!(ESCAPES, [0xff, 0xfe, 0x01, 0x00]);
+    assert_eq!(ONE, [b'z']);
+    assert_eq!(NONE.len(), 0);
+
+    let local = reverse_bytes!(b"hello");
+    assert_eq!(local.len(), 5);
+    assert_eq!(&local[], &b"olleh"[]);
+
+    // reversing twice gives back the original
+    let mut twice = reverse_bytes!(b"round trip");
+    twice.reverse();
+    assert_eq!(&twice[], &b"round trip"[]);
+}
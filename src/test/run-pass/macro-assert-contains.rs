@@ -0,0 +1,50 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::boxed::BoxAny;
+use std::thread::Thread;
+
+fn panic_message<F: FnOnce() + Send>(f: F) -> String {
+    let err = Thread::scoped(f).join().err().unwrap();
+    *err.downcast::<String>().ok().unwrap()
+}
+
+fn main() {
+    // substrings
+    let s = String::from_str("the quick brown fox");
+    assert_contains!(s, "quick");
+    assert_contains!(s, String::from_str("fox"));
+    assert_contains!("abc", 'b');
+    assert_contains!(&s[4..], "", "the empty string is in everything");
+
+    // elements
+    let v = vec![1, 2, 3];
+    assert_contains!(v, 3);
+    assert_contains!([Some('a'), None], None::<char>);
+    assert_contains!(&v[..1], 1);
+    // both are only borrowed
+    assert_eq!((s.len(), v.len()), (19, 3));
+
+    // failures
+    let msg = panic_message(|| {
+        let s = "hello";
+        assert_contains!(s, "world");
+    });
+    assert_eq!(msg, "assertion failed: `s` does not contain `\"world\"` \
+                     (haystack: `\"hello\"`, needle: `\"world\"`)");
+
+    let msg = panic_message(|| {
+        let v = vec![1, 2, 3];
+        let x = 4;
+        assert_contains!(v, x, "looking for {}", "four");
+    });
+    assert_eq!(msg, "assertion failed: `v` does not contain `x` \
+                     (haystack: `[1, 2, 3]`, needle: `4`): looking for four");
+}
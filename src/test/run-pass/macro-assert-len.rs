@@ -0,0 +1,50 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::boxed::BoxAny;
+use std::collections::HashMap;
+use std::thread::Thread;
+
+fn panic_message<F: FnOnce() + Send>(f: F) -> String {
+    let err = Thread::scoped(f).join().err().unwrap();
+    *err.downcast::<String>().ok().unwrap()
+}
+
+fn main() {
+    let v = vec![1, 2, 3];
+    assert_len!(v, 3);
+    assert_len!(&v[1..], 2);
+    assert_len!([0u8; 0], 0);
+    assert_len!("héllo".to_string(), 6);
+    let mut m = HashMap::new();
+    m.insert("a", 1);
+    assert_len!(m, 1, "one entry");
+    // the collection is only borrowed
+    assert_eq!(v.len(), 3);
+
+    let msg = panic_message(|| {
+        let v = vec![1, 2, 3];
+        assert_len!(v, 2);
+    });
+    assert_eq!(msg, "assertion failed: `v` has length 3, expected 2 (`[1, 2, 3]`)");
+
+    let msg = panic_message(|| {
+        let name = "abc";
+        assert_len!(name, 4, "bad name {}", 7);
+    });
+    assert_eq!(msg, "assertion failed: `name` has length 3, expected 4 (`\"abc\"`): bad name 7");
+
+    // long collections aren't printed
+    let msg = panic_message(|| {
+        let big = (0..100).collect::<Vec<i32>>();
+        assert_len!(big, 10);
+    });
+    assert_eq!(msg, "assertion failed: `big` has length 100, expected 10");
+}
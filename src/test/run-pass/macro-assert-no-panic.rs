@@ -0,0 +1,45 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::boxed::BoxAny;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::thread::Thread;
+
+fn panic_message<F: FnOnce() + Send>(f: F) -> String {
+    let err = Thread::scoped(f).join().err().unwrap();
+    *err.downcast::<String>().ok().unwrap()
+}
+
+fn main() {
+    // a clean block gives its value, and may borrow
+    let v = vec![1, 2, 3];
+    let sum = assert_no_panic!({ v.iter().fold(0, |a, &b| a + b) });
+    assert_eq!(sum, 6);
+    let shared = Rc::new(RefCell::new(0));
+    assert_no_panic!({ *shared.borrow_mut() += 1 });
+    assert_eq!(*shared.borrow(), 1);
+    assert_no_panic!({});
+    assert_eq!(v.len(), 3);
+
+    // a panicking block is reported with the original message
+    let msg = panic_message(|| {
+        assert_no_panic!({ if v.len() > 2 { panic!("too many: {}", v.len()) } });
+    });
+    assert!(msg.starts_with("assertion failed: block panicked at "), "{}", msg);
+    assert!(msg.contains(&format!("{}:", file!())[]), "{}", msg);
+    assert!(msg.ends_with(": too many: 3"), "{}", msg);
+
+    let msg = panic_message(|| {
+        let none: Option<u32> = None;
+        assert_no_panic!({ none.expect("nothing there") });
+    });
+    assert!(msg.ends_with(": nothing there"), "{}", msg);
+}
@@ -0,0 +1,55 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::boxed::BoxAny;
+use std::thread::Thread;
+
+fn panic_message<F: FnOnce() + Send>(f: F) -> String {
+    let err = Thread::scoped(f).join().err().unwrap();
+    *err.downcast::<String>().ok().unwrap()
+}
+
+fn main() {
+    // sorted sequences pass
+    assert_sorted!(vec![1, 2, 2, 3]);
+    assert_sorted!([0.5f64, 1.0, 1.5]);
+    assert_sorted!(Vec::<i32>::new());
+    let words = vec!["apple", "banana", "cherry"];
+    assert_sorted!(words);
+    assert_sorted!(&words[1..]);
+
+    let msg = panic_message(|| {
+        let v = vec![1, 3, 7, 5, 2];
+        assert_sorted!(v);
+    });
+    assert_eq!(msg, "assertion failed: `v` is not sorted: element 3 (`5`) \
+                     is out of order after `7`");
+
+    // with a comparator
+    let pairs = [(1, 'z'), (2, 'a'), (2, 'b'), (5, 'a')];
+    assert_sorted_by!(pairs, |a, b| a.0.cmp(&b.0));
+    assert_sorted_by!(words, |a, b| a.len().cmp(&b.len()));
+    let msg = panic_message(|| {
+        let by_len = ["ab", "a"];
+        assert_sorted_by!(by_len, |a, b| a.len().cmp(&b.len()));
+    });
+    assert_eq!(msg, "assertion failed: `by_len` is not sorted: element 1 (`\"a\"`) \
+                     is out of order after `\"ab\"`");
+
+    // descending order
+    assert_sorted_desc!(vec![9, 4, 4, 1]);
+    assert_sorted_desc!([1]);
+    let msg = panic_message(|| {
+        let letters = vec!['c', 'b', 'd'];
+        assert_sorted_desc!(letters);
+    });
+    assert_eq!(msg, "assertion failed: `letters` is not sorted in descending \
+                     order: element 2 (`'d'`) is out of order after `'b'`");
+}
@@ -0,0 +1,47 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![allow(unknown_features)]
+#![feature(box_syntax)]
+
+use std::num::Float;
+use std::old_io::{ChanReader, ChanWriter, stdio};
+use std::sync::mpsc::channel;
+use std::thread::Thread;
+
+fn main() {
+    let (tx, rx) = channel();
+    let (mut r, w) = (ChanReader::new(rx), ChanWriter::new(tx));
+    let t = Thread::scoped(move|| {
+        stdio::set_stderr(box w);
+        let mut light_calls = 0u32;
+        let mut heavy_calls = 0u32;
+        let (light, heavy) = bench_compare!(200,
+            "light" => || { light_calls += 1; 1u32 },
+            "heavy" => || {
+                heavy_calls += 1;
+                (0..1000u32).map(|x| x * x).fold(0, |a, b| a ^ b)
+            },
+        );
+        assert_eq!((light_calls, heavy_calls), (200, 200));
+        for &ns in [light, heavy].iter() {
+            assert!(ns > 0.0 && ns.is_finite(), "bad timing {}", ns);
+        }
+    });
+    assert!(t.join().is_ok());
+
+    let out = r.read_to_string().unwrap();
+    let lines = out.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].starts_with("light: 200 iters, "), "{}", lines[0]);
+    assert!(lines[1].starts_with("heavy: 200 iters, "), "{}", lines[1]);
+    assert!(lines[2].starts_with("light vs heavy: "), "{}", lines[2]);
+    assert!(lines[2].ends_with("x speedup"), "{}", lines[2]);
+}
@@ -0,0 +1,41 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![allow(unknown_features)]
+#![feature(box_syntax)]
+
+use std::num::Float;
+use std::old_io::{ChanReader, ChanWriter, stdio};
+use std::sync::mpsc::channel;
+use std::thread::Thread;
+
+fn main() {
+    let (tx, rx) = channel();
+    let (mut r, w) = (ChanReader::new(rx), ChanWriter::new(tx));
+    let t = Thread::scoped(move|| {
+        stdio::set_stderr(box w);
+        let mut calls = 0;
+        let ns = benchmark!("sort", 200, || {
+            calls += 1;
+            let mut v = (0..500u32).map(|i| (i * 7919) % 500).collect::<Vec<_>>();
+            v.sort();
+            v
+        });
+        assert_eq!(calls, 200);
+        ns
+    });
+    let ns = t.join().ok().unwrap();
+    assert!(ns > 0.0);
+    assert!(ns.is_finite());
+
+    let out = r.read_to_string().unwrap();
+    assert!(out.starts_with("sort: 200 iters, "));
+    assert!(out.ends_with(" ns/iter\n"));
+}
@@ -0,0 +1,39 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::fmt;
+
+struct Point { x: i32, y: i32 }
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+fn main() {
+    let points = vec![Point { x: 1, y: 2 }, Point { x: -3, y: 0 }];
+    let s = build_string!(|out| {
+        try!(writeln!(out, "{} points", points.len()));
+        for p in points.iter() {
+            try!(write!(out, "{};", p));
+        }
+        try!(write!(out, "{:>4}", 7));
+    });
+    assert_eq!(s, "2 points\n(1, 2);(-3, 0);   7");
+
+    // the body may end with a write
+    let name = "world";
+    assert_eq!(build_string!(|s| write!(s, "hello {}", name)), "hello world");
+
+    // or not write at all
+    assert_eq!(build_string!(|s| { s.push('x'); }), "x");
+    assert_eq!(build_string!(|_s| {}), "");
+}
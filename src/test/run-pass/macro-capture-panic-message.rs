@@ -0,0 +1,59 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering, ATOMIC_USIZE_INIT};
+use std::thread::Thread;
+
+static RAN: AtomicUsize = ATOMIC_USIZE_INIT;
+
+fn main() {
+    // a block which doesn't panic gives its value
+    let words = vec!["a", "bb"];
+    let r = capture_panic_message!({
+        RAN.fetch_add(1, Ordering::SeqCst);
+        words.iter().fold(0, |n, w| n + w.len())
+    });
+    assert_eq!(r, Ok(3));
+    assert_eq!(RAN.load(Ordering::SeqCst), 1);
+    // the block only borrowed this
+    assert_eq!(words.len(), 2);
+
+    // string payloads have their message recovered
+    let r: Result<(), String> = capture_panic_message!({ panic!("static message") });
+    assert_eq!(r, Err("static message".to_string()));
+    let r: Result<(), String> = capture_panic_message!({ panic!("formatted {}", 7) });
+    assert_eq!(r, Err("formatted 7".to_string()));
+    let r: Result<(), String> = capture_panic_message!({ panic!(String::from_str("owned")) });
+    assert_eq!(r, Err("owned".to_string()));
+    let r = capture_panic_message!({ words[5] });
+    assert!(r.unwrap_err().contains("out of bounds"));
+
+    // others get a generic message
+    let r: Result<(), String> = capture_panic_message!({ panic!(42) });
+    assert_eq!(r, Err("Box<Any>".to_string()));
+
+    // the block runs on this thread, so it may use values which aren't Send,
+    // and see what it did to them before panicking
+    let log = Rc::new(RefCell::new(Vec::new()));
+    let r = capture_panic_message!({
+        log.borrow_mut().push("before");
+        if log.borrow().len() == 1 { panic!("midway") }
+        log.borrow_mut().push("after");
+    });
+    assert_eq!(r, Err("midway".to_string()));
+    assert_eq!(*log.borrow(), vec!["before"]);
+
+    // and the thread carries on as if nothing had happened
+    assert!(!Thread::panicking());
+    let r: Result<(), String> = capture_panic_message!({ panic!("again") });
+    assert_eq!(r, Err("again".to_string()));
+}
@@ -0,0 +1,34 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::cell::Cell;
+use std::iter;
+
+fn main() {
+    let a = vec![1, 2, 3];
+    let b = vec![4];
+    let c = vec![5, 6];
+    let all = chain!(a.clone(), b.clone(), c.clone()).collect::<Vec<_>>();
+    assert_eq!(all, vec![1, 2, 3, 4, 5, 6]);
+    assert_eq!(chain!(a.iter(), b.iter(), c.iter(),).count(), 6);
+
+    assert_eq!(chain!(Vec::<i32>::new()).next(), None);
+    assert_eq!(chain!(Vec::new(), b, Vec::new()).collect::<Vec<_>>(), vec![4]);
+
+    // nothing is evaluated ahead of time, so infinite iterators are fine
+    let pulled = Cell::new(0);
+    let counted = a.iter().map(|&x| { pulled.set(pulled.get() + 1); x });
+    let mut it = chain!(counted, iter::repeat(0));
+    assert_eq!(pulled.get(), 0);
+    assert_eq!(it.next(), Some(1));
+    assert_eq!(pulled.get(), 1);
+    assert_eq!(it.by_ref().take(5).collect::<Vec<_>>(), vec![2, 3, 0, 0, 0]);
+    assert_eq!(pulled.get(), 3);
+}
@@ -0,0 +1,40 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::cell::Cell;
+use std::cmp::Ordering::{self, Less, Equal, Greater};
+
+fn counted(counter: &Cell<u32>, a: i32, b: i32) -> Ordering {
+    counter.set(counter.get() + 1);
+    a.cmp(&b)
+}
+
+fn main() {
+    let n = Cell::new(0);
+
+    // the first key decides, so nothing else is evaluated
+    assert_eq!(chained_cmp!(counted(&n, 1, 2), counted(&n, 5, 0), counted(&n, 5, 0)),
+               Less);
+    assert_eq!(n.get(), 1);
+
+    // a later key decides
+    n.set(0);
+    assert_eq!(chained_cmp!(counted(&n, 1, 1), counted(&n, 3, 2), counted(&n, 0, 9)),
+               Greater);
+    assert_eq!(n.get(), 2);
+
+    // everything is equal
+    n.set(0);
+    assert_eq!(chained_cmp!(counted(&n, 1, 1), counted(&n, 2, 2), counted(&n, 3, 3),),
+               Equal);
+    assert_eq!(n.get(), 3);
+
+    assert_eq!(chained_cmp!("b".cmp("a")), Greater);
+}
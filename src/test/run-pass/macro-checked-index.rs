@@ -0,0 +1,34 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+fn main() {
+    let v = vec![1, 2, 3, 4];
+    assert_eq!(checked_index!(v, 0), Some(&1));
+    assert_eq!(checked_index!(v, 3), Some(&4));
+    assert_eq!(checked_index!(v, 4), None);
+    assert_eq!(checked_index!(v, 100), None);
+
+    let empty: &[u8] = &[];
+    assert_eq!(checked_index!(empty, 0), None);
+
+    assert_eq!(checked_index!(v, 1..3), Some(&[2, 3][]));
+    assert_eq!(checked_index!(v, 4..4), Some(&[][]));
+    assert_eq!(checked_index!(v, 2..5), None);
+    assert_eq!(checked_index!(v, 3..1), None);
+    assert_eq!(checked_index!(v, 2..), Some(&[3, 4][]));
+    assert_eq!(checked_index!(v, 5..), None);
+    assert_eq!(checked_index!(v, ..2), Some(&[1, 2][]));
+    assert_eq!(checked_index!(v, ..5), None);
+    assert_eq!(checked_index!(empty, ..), Some(empty));
+
+    let arr = ["a", "b"];
+    assert_eq!(checked_index!(arr, 1), Some(&"b"));
+    assert_eq!(checked_index!(&arr[], 0..1), Some(&["a"][]));
+}
@@ -0,0 +1,47 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::cell::Cell;
+use std::thread::Thread;
+
+fn counted<T>(counter: &Cell<u32>, x: T) -> T {
+    counter.set(counter.get() + 1);
+    x
+}
+
+fn main() {
+    // below, within and above the range
+    assert_eq!(clamp!(-5, 0, 10), 0);
+    assert_eq!(clamp!(0, 0, 10), 0);
+    assert_eq!(clamp!(7, 0, 10), 7);
+    assert_eq!(clamp!(10, 0, 10), 10);
+    assert_eq!(clamp!(11, 0, 10), 10);
+    assert_eq!(clamp!(3, 3, 3), 3);
+
+    assert_eq!(clamp!(1.5f64, 0.0, 1.0), 1.0);
+    assert_eq!(clamp!(0.25f64, 0.0, 1.0), 0.25);
+    assert_eq!(clamp!("m", "a", "k"), "k");
+
+    // each argument is evaluated exactly once
+    let n = Cell::new(0);
+    assert_eq!(clamp!(counted(&n, 20), counted(&n, 1), counted(&n, 9)), 9);
+    assert_eq!(n.get(), 3);
+    n.set(0);
+    assert_eq!(clamp!(counted(&n, 5), 1, 9), 5);
+    assert_eq!(n.get(), 1);
+
+    // inverted bounds are caught in debug builds
+    let result = Thread::scoped(|| clamp!(5, 10, 0)).join();
+    if cfg!(ndebug) {
+        assert_eq!(result.ok(), Some(10));
+    } else {
+        assert!(result.is_err());
+    }
+}
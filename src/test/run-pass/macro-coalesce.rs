@@ -0,0 +1,41 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::cell::Cell;
+
+fn counted(calls: &Cell<u32>, value: Option<i32>) -> Option<i32> {
+    calls.set(calls.get() + 1);
+    value
+}
+
+fn main() {
+    // the first is `Some`, so nothing else is evaluated
+    let calls = Cell::new(0);
+    assert_eq!(coalesce!(counted(&calls, Some(1)), counted(&calls, Some(2))), Some(1));
+    assert_eq!(calls.get(), 1);
+
+    // a later one is `Some`
+    calls.set(0);
+    assert_eq!(coalesce!(counted(&calls, None),
+                         counted(&calls, None),
+                         counted(&calls, Some(3)),
+                         counted(&calls, Some(4))),
+               Some(3));
+    assert_eq!(calls.get(), 3);
+
+    // all are `None`
+    calls.set(0);
+    assert_eq!(coalesce!(counted(&calls, None), counted(&calls, None),), None);
+    assert_eq!(calls.get(), 2);
+
+    let name: Option<String> = None;
+    assert_eq!(coalesce!(name, Some("anonymous".to_string())), Some("anonymous".to_string()));
+    assert_eq!(coalesce!(Some('x')), Some('x'));
+}
@@ -0,0 +1,30 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::cell::Cell;
+
+fn main() {
+    assert_eq!(count_while!(vec![2, 4, 6, 7, 8], |x| *x % 2 == 0), 3);
+    assert_eq!(count_while!("0042abc".chars(), |c| c.is_digit(10)), 4);
+
+    // everything matches
+    assert_eq!(count_while!(vec![1, 2, 3], |x| *x > 0), 3);
+    assert_eq!(count_while!(Vec::<i32>::new(), |_| false), 0);
+
+    // nothing matches
+    assert_eq!(count_while!([5, 1, 1].iter(), |x| **x < 5), 0);
+
+    // nothing is taken after the first non-matching element
+    let seen = Cell::new(0);
+    let mut it = (0..10).inspect(|_| seen.set(seen.get() + 1));
+    assert_eq!(count_while!(it.by_ref(), |x| *x < 3), 3);
+    assert_eq!(seen.get(), 4);
+    assert_eq!(it.next(), Some(4));
+}
@@ -0,0 +1,60 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![allow(unknown_features)]
+#![feature(box_syntax)]
+
+use std::env;
+use std::old_io::{ChanReader, ChanWriter, stdio};
+use std::sync::mpsc::channel;
+use std::thread::Thread;
+
+fn capture<F: FnOnce() + Send + 'static>(f: F) -> String {
+    let (tx, rx) = channel();
+    let (mut r, w) = (ChanReader::new(rx), ChanWriter::new(tx));
+    let _t = Thread::spawn(move|| {
+        stdio::set_stdout(box w);
+        f();
+    });
+    r.read_to_string().unwrap()
+}
+
+fn main() {
+    // a replaced stdout isn't a terminal, so nothing is coloured
+    env::remove_var("CLICOLOR_FORCE");
+    let out = capture(|| {
+        assert!(!stdio::stdout_isatty());
+        cprintln!(red, "{} failed", 3);
+        cprintln!(green, "ok");
+    });
+    assert_eq!(out, "3 failed\nok\n");
+
+    // unless colour is forced
+    env::set_var("CLICOLOR_FORCE", "1");
+    let out = capture(|| {
+        cprintln!(red, "{} failed", 3);
+        cprintln!(blue, "plain {}", "text",);
+    });
+    assert_eq!(out, "\x1b[31m3 failed\x1b[0m\n\x1b[34mplain text\x1b[0m\n");
+
+    env::set_var("CLICOLOR_FORCE", "0");
+    let out = capture(|| cprintln!(cyan, "off"));
+    assert_eq!(out, "off\n");
+
+    // handing the default handle back to `set_stdout` restores it
+    Thread::scoped(|| {
+        let (tx, _rx) = channel();
+        print!("");
+        let default = stdio::set_stdout(box ChanWriter::new(tx)).unwrap();
+        assert!(!stdio::stdout_isatty());
+        drop(stdio::set_stdout(default));
+        assert_eq!(stdio::stdout_isatty(), stdio::stdout_raw().isatty());
+    }).join().ok().unwrap();
+}
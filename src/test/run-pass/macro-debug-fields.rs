@@ -0,0 +1,38 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+struct Account {
+    id: u32,
+    owner: String,
+    secret: Vec<u8>,
+    balance: i64,
+}
+
+debug_fields!(Account { id, owner => "name", balance });
+
+struct Reordered { a: u8, b: (bool, char) }
+debug_fields!(Reordered { b => "pair", a, });
+
+struct Opaque { _handle: usize }
+debug_fields!(Opaque {});
+
+fn main() {
+    let acct = Account { id: 7, owner: "ann".to_string(), secret: vec![1, 2, 3], balance: -20 };
+    let s = format!("{:?}", acct);
+    assert_eq!(s, "Account { id: 7, name: \"ann\", balance: -20 }");
+    assert!(!s.contains("secret"));
+    assert_eq!(acct.secret.len(), 3);
+
+    let r = Reordered { a: 1, b: (true, 'x') };
+    assert_eq!(format!("{:?}", r), "Reordered { pair: (true, 'x'), a: 1 }");
+    assert_eq!(r.a, 1);
+
+    assert_eq!(format!("{:?}", Opaque { _handle: 0 }), "Opaque");
+}
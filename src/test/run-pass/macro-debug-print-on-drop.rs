@@ -0,0 +1,51 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![allow(unknown_features)]
+#![feature(box_syntax)]
+
+use std::old_io::{ChanReader, ChanWriter, stdio};
+use std::sync::mpsc::channel;
+use std::thread::Thread;
+
+fn main() {
+    let (tx, rx) = channel();
+    let (mut r, w) = (ChanReader::new(rx), ChanWriter::new(tx));
+    let _t = Thread::spawn(move|| {
+        stdio::set_stderr(box w);
+        {
+            let first = debug_print_on_drop!(first, vec![1, 2, 3]);
+            let mut second = debug_print_on_drop!(second, String::new());
+            // the guards are used just like the values they hold
+            second.push_str("hi");
+            assert_eq!(first.len(), 3);
+            assert_eq!(&second[], "hi");
+            stdio::eprintln_args(format_args!("end of scope"));
+        }
+        let third = debug_print_on_drop!(third, 3);
+        assert_eq!(*third + 1, 4);
+        drop(third);
+        stdio::eprintln_args(format_args!("done"));
+    });
+
+    let out = r.read_to_string().unwrap();
+    let lines = out.lines().collect::<Vec<_>>();
+    if cfg!(ndebug) {
+        assert_eq!(lines, vec!["end of scope", "done"]);
+    } else {
+        assert_eq!(lines, vec![
+            "end of scope",
+            "dropping second",
+            "dropping first",
+            "dropping third",
+            "done",
+        ]);
+    }
+}
@@ -0,0 +1,54 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::sync::mpsc::{channel, Sender};
+use std::thread::Thread;
+
+fn work(tx: &Sender<&'static str>, fail: bool) {
+    defer_on_unwind!({ tx.send("cleanup").unwrap(); });
+    tx.send("start").unwrap();
+    if fail {
+        panic!("failed");
+    }
+    tx.send("done").unwrap();
+}
+
+// Sends "inner" from a guard made while the thread is already panicking.
+struct Nested(Sender<&'static str>);
+
+impl Drop for Nested {
+    fn drop(&mut self) {
+        let tx = &self.0;
+        defer_on_unwind!({ tx.send("inner").unwrap(); });
+        tx.send("nested drop").unwrap();
+    }
+}
+
+fn main() {
+    // a normal return leaves the block unrun
+    let (tx, rx) = channel();
+    assert!(Thread::scoped(move|| work(&tx, false)).join().is_ok());
+    assert_eq!(rx.iter().collect::<Vec<_>>(), vec!["start", "done"]);
+
+    // a panic runs it, and the panic is still caught outside
+    let (tx, rx) = channel();
+    let res = Thread::scoped(move|| work(&tx, true)).join();
+    assert!(res.is_err());
+    assert_eq!(rx.iter().collect::<Vec<_>>(), vec!["start", "cleanup"]);
+
+    // a guard made during unwinding doesn't run
+    let (tx, rx) = channel();
+    let res = Thread::scoped(move|| {
+        let _n = Nested(tx);
+        panic!("outer");
+    }).join();
+    assert!(res.is_err());
+    assert_eq!(rx.iter().collect::<Vec<_>>(), vec!["nested drop"]);
+}
@@ -0,0 +1,43 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::fmt;
+
+struct Point(i32, i32);
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // written in several pieces, which must all be counted
+        write!(f, "({}, {})", self.0, self.1)
+    }
+}
+
+macro_rules! check {
+    ($x:expr) => (assert_eq!(display_len!($x), format!("{}", $x).chars().count()))
+}
+
+fn main() {
+    check!("hello");
+    check!("");
+    check!("héllo wörld");
+    check!("日本語");
+    check!('ß');
+    check!(-1234i32);
+    check!(1.5f64);
+    check!(Point(-3, 40));
+
+    assert_eq!(display_len!("日本語"), 3);
+    assert_eq!(display_len!(Point(-3, 40)), 8);
+
+    // the value is only borrowed
+    let s = String::from_str("naïve");
+    assert_eq!(display_len!(s), 5);
+    assert_eq!(s, "naïve");
+}
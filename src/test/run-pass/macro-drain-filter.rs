@@ -0,0 +1,41 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::sync::{Arc, Mutex};
+use std::thread::Thread;
+
+fn main() {
+    // remove every other element
+    let mut v = (0..10).collect::<Vec<u32>>();
+    let mut i = 0;
+    let removed = drain_filter!(v, |_| { i += 1; i % 2 == 0 });
+    assert_eq!(v, vec![0, 2, 4, 6, 8]);
+    assert_eq!(removed, vec![1, 3, 5, 7, 9]);
+
+    // nothing matches
+    let mut v = vec!["a".to_string(), "b".to_string()];
+    let removed = drain_filter!(v, |s| s.len() > 1);
+    assert_eq!(v, vec!["a".to_string(), "b".to_string()]);
+    assert!(removed.is_empty());
+
+    // a panicking predicate leaves the vector alone
+    let shared = Arc::new(Mutex::new(vec![1, 2, 3, 4]));
+    let shared2 = shared.clone();
+    let res = Thread::scoped(move|| {
+        let mut v = shared2.lock().unwrap();
+        drain_filter!(*v, |&x| if x == 3 { panic!() } else { x == 1 });
+    }).join();
+    assert!(res.is_err());
+    let v = match shared.lock() {
+        Ok(v) => v,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    assert_eq!(*v, vec![1, 2, 3, 4]);
+}
@@ -0,0 +1,37 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::env;
+
+fn main() {
+    env::set_var("MACRO_ENV_LIST_HOSTS", " alpha, beta ,,gamma.example.com , ");
+    assert_eq!(env_list!("MACRO_ENV_LIST_HOSTS", ','),
+               vec!["alpha", "beta", "gamma.example.com"]);
+    // any delimiter `split` accepts
+    assert_eq!(env_list!("MACRO_ENV_LIST_HOSTS", '.'),
+               vec!["alpha, beta ,,gamma", "example", "com ,"]);
+
+    env::remove_var("MACRO_ENV_LIST_UNSET");
+    assert!(env_list!("MACRO_ENV_LIST_UNSET", ',').is_empty());
+    assert_eq!(env_list!("MACRO_ENV_LIST_UNSET", ',', u16), Ok(vec![]));
+
+    env::set_var("MACRO_ENV_LIST_EMPTY", " ; ;");
+    assert!(env_list!("MACRO_ENV_LIST_EMPTY", ';').is_empty());
+
+    env::set_var("MACRO_ENV_LIST_PORTS", "80;443; 8080");
+    assert_eq!(env_list!("MACRO_ENV_LIST_PORTS", ';', u16), Ok(vec![80, 443, 8080]));
+    let ports: Result<Vec<i64>, _> = env_list!("MACRO_ENV_LIST_PORTS", ';', i64);
+    assert_eq!(ports.unwrap().len(), 3);
+
+    env::set_var("MACRO_ENV_LIST_PORTS", "80, http, 70000");
+    assert!(env_list!("MACRO_ENV_LIST_PORTS", ',', u16).is_err());
+    assert_eq!(env_list!("MACRO_ENV_LIST_PORTS", ',', String).unwrap(),
+               vec!["80", "http", "70000"]);
+}
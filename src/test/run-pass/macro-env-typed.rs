@@ -0,0 +1,47 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::env::{self, ParseVarError, VarError};
+
+fn main() {
+    env::set_var("MACRO_ENV_TYPED_PORT", "8081");
+    env::remove_var("MACRO_ENV_TYPED_MISSING");
+    env::set_var("MACRO_ENV_TYPED_BAD", "eighty");
+
+    // a valid value
+    assert_eq!(env_typed!("MACRO_ENV_TYPED_PORT", u16), Ok(8081));
+    assert_eq!(env_typed!("MACRO_ENV_TYPED_PORT", u16, 80), 8081);
+    assert_eq!(env_typed!("MACRO_ENV_TYPED_PORT", String).unwrap(), "8081");
+
+    // a missing variable
+    assert_eq!(env_typed!("MACRO_ENV_TYPED_MISSING", u16, 80), 80);
+    match env_typed!("MACRO_ENV_TYPED_MISSING", u16) {
+        Err(ParseVarError::Var(VarError::NotPresent)) => {}
+        other => panic!("expected a missing variable, got {:?}", other),
+    }
+
+    // an unparseable value
+    assert_eq!(env_typed!("MACRO_ENV_TYPED_BAD", u16, 80), 80);
+    match env_typed!("MACRO_ENV_TYPED_BAD", u16) {
+        Err(ParseVarError::Parse(..)) => {}
+        other => panic!("expected a parse error, got {:?}", other),
+    }
+    // out of range for the type is a parse error too
+    env::set_var("MACRO_ENV_TYPED_BAD", "70000");
+    assert!(env_typed!("MACRO_ENV_TYPED_BAD", u16).is_err());
+    assert_eq!(env_typed!("MACRO_ENV_TYPED_BAD", u32), Ok(70000));
+
+    // the default is only evaluated when needed
+    let mut defaults = 0;
+    env_typed!("MACRO_ENV_TYPED_PORT", u16, { defaults += 1; 0 });
+    assert_eq!(defaults, 0);
+    env_typed!("MACRO_ENV_TYPED_MISSING", u16, { defaults += 1; 0 });
+    assert_eq!(defaults, 1);
+}
@@ -0,0 +1,28 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+fn main() {
+    assert_eq!(escape!("plain text"), "plain text");
+    assert_eq!(escape!(r#"a "quoted" and 'single' word"#),
+               r#"a \"quoted\" and \'single\' word"#);
+    assert_eq!(escape!("one\ntwo\r\n"), r"one\ntwo\r\n");
+    assert_eq!(escape!("a\tb\\c"), r"a\tb\\c");
+    assert_eq!(escape!("\x07 bell"), r"\u{7} bell");
+    assert_eq!(escape!("café ☃"), r"caf\u{e9} \u{2603}");
+
+    // a String is only borrowed
+    let s = "line\n".to_string();
+    assert_eq!(escape!(s), r"line\n");
+    assert_eq!(s, "line\n");
+
+    // the same as Debug, less the quotes
+    let s = "tab\t \"quote\" ü\n";
+    assert_eq!(format!("\"{}\"", escape!(s)), format!("{:?}", s));
+}
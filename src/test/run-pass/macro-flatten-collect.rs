@@ -0,0 +1,25 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+fn main() {
+    let nested = vec![vec![1, 2, 3], vec![], vec![4], vec![5, 6]];
+    let flat = flatten_collect!(nested);
+    assert_eq!(flat, vec![1, 2, 3, 4, 5, 6]);
+    assert_eq!(flat.len(), 6);
+
+    let ranges = [0..3, 10..12, 5..5, 7..8];
+    let flat = flatten_collect!(ranges.iter().cloned());
+    assert_eq!(flat, vec![0, 1, 2, 10, 11, 7]);
+    // the ranges give exact size hints, so the vector is allocated once
+    assert_eq!(flat.capacity(), 6);
+
+    let empty: Vec<Vec<u8>> = vec![];
+    assert!(flatten_collect!(empty).is_empty());
+}
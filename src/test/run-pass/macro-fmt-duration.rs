@@ -0,0 +1,48 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![allow(unknown_features)]
+#![feature(box_syntax)]
+
+use std::old_io::{ChanReader, ChanWriter, stdio};
+use std::sync::mpsc::channel;
+use std::thread::Thread;
+use std::time::Duration;
+
+fn main() {
+    // hours, minutes and seconds
+    let d = Duration::hours(1) + Duration::minutes(2) + Duration::milliseconds(3004);
+    assert_eq!(fmt_duration!(d), "1h2m3.004s");
+    assert_eq!(fmt_duration!(Duration::hours(26)), "26h0m0.000s");
+    assert_eq!(fmt_duration!(Duration::hours(1) + Duration::seconds(5)), "1h0m5.000s");
+    assert_eq!(fmt_duration!(Duration::seconds(61)), "1m1.000s");
+
+    // seconds and milliseconds
+    assert_eq!(fmt_duration!(Duration::milliseconds(3004)), "3.004s");
+    assert_eq!(fmt_duration!(Duration::milliseconds(500)), "0.500s");
+    assert_eq!(fmt_duration!(Duration::milliseconds(1)), "0.001s");
+
+    // less than a millisecond
+    assert_eq!(fmt_duration!(Duration::microseconds(999)), "999us");
+    assert_eq!(fmt_duration!(Duration::nanoseconds(1500)), "1us");
+    assert_eq!(fmt_duration!(Duration::nanoseconds(40)), "40ns");
+    assert_eq!(fmt_duration!(Duration::zero()), "0ns");
+
+    assert_eq!(fmt_duration!(-Duration::milliseconds(1250)), "-1.250s");
+
+    let (tx, rx) = channel();
+    let (mut r, w) = (ChanReader::new(rx), ChanWriter::new(tx));
+    let _t = Thread::spawn(move|| {
+        stdio::set_stderr(box w);
+        print_duration!(Duration::minutes(3) + Duration::milliseconds(20));
+        print_duration!(Duration::microseconds(12));
+    });
+    assert_eq!(r.read_to_string().unwrap(), "3m0.020s\n12us\n");
+}
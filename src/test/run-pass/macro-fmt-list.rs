@@ -0,0 +1,33 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::fmt;
+
+struct Point(i32, i32);
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.0, self.1)
+    }
+}
+
+fn main() {
+    let empty: Vec<u32> = Vec::new();
+    assert_eq!(fmt_list!(&empty, ", "), "");
+    assert_eq!(fmt_list!(vec!["one"], ", "), "one");
+    assert_eq!(fmt_list!(vec!["a", "b", "c"], ", "), "a, b, c");
+    assert_eq!(fmt_list!(1..5, " -> "), "1 -> 2 -> 3 -> 4");
+
+    let points = [Point(0, 1), Point(2, 3)];
+    assert_eq!(fmt_list!(&points, "; "), "(0, 1); (2, 3)");
+    // the separator is a plain string which may be computed
+    let sep = "|".to_string();
+    assert_eq!(fmt_list!(&points, &sep[]), "(0, 1)|(2, 3)");
+}
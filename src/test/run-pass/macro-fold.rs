@@ -0,0 +1,35 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+fn add(a: i32, b: &i32) -> i32 { a + *b }
+
+fn main() {
+    let v = vec![1, 2, 3, 4, 5];
+
+    assert_eq!(fold!(v.iter(), 0, |acc, &x| acc + x), 15);
+    assert_eq!(fold!(v.iter(), 1, |acc, &x| acc * x), 120);
+    assert_eq!(fold!(v.iter(), 0, add), 15);
+    assert_eq!(fold!(Vec::<i32>::new(), 7, |acc, x| acc + x), 7);
+
+    // block bodies, with an immutable and a mutable accumulator
+    assert_eq!(fold!(v.iter(), 0, acc, &x => {
+        let sq = x * x;
+        acc + sq
+    }), 55);
+
+    let s = fold!(v, String::new(), mut acc, x => {
+        if !acc.is_empty() {
+            acc.push('-');
+        }
+        acc.push_str(&x.to_string()[]);
+        acc
+    });
+    assert_eq!(s, "1-2-3-4-5");
+}
@@ -0,0 +1,45 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::fmt;
+
+// Writes itself without looking at the formatter's width.
+struct Tag(u32);
+
+impl fmt::Display for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
+fn main() {
+    assert_eq!(format_aligned!("abc", 6, true), "abc   ");
+    assert_eq!(format_aligned!("abc", 6, false), "   abc");
+    assert_eq!(format_aligned!(-1.5f64, 6, false), "  -1.5");
+    assert_eq!(format_aligned!(Tag(7), 4, false), "  #7");
+    assert_eq!(format_aligned!(Tag(7), 4, true), "#7  ");
+
+    // the width is chosen at runtime
+    let names = ["a", "bbbb", "cc"];
+    let width = names.iter().map(|n| n.len()).max().unwrap();
+    let column = names.iter().map(|n| format_aligned!(n, width, true)).collect::<Vec<_>>();
+    assert_eq!(column, vec!["a   ", "bbbb", "cc  "]);
+
+    // wider values aren't truncated
+    assert_eq!(format_aligned!("toolong", 3, true), "toolong");
+    assert_eq!(format_aligned!(123456, 3, false), "123456");
+    assert_eq!(format_aligned!("", 0, false), "");
+
+    // padding counts characters, not bytes
+    let s = format_aligned!("héllo", 7, false);
+    assert_eq!(s, "  héllo");
+    assert_eq!(s.chars().count(), 7);
+    assert_eq!(format_aligned!("日本", 4, true), "日本  ");
+}
@@ -0,0 +1,37 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::u64;
+
+fn main() {
+    // binary units
+    assert_eq!(format_si!(0), "0 B");
+    assert_eq!(format_si!(1023), "1023 B");
+    assert_eq!(format_si!(1024), "1.0 KiB");
+    assert_eq!(format_si!(1536u32), "1.5 KiB");
+    // just under a unit rounds up into it
+    assert_eq!(format_si!(1048575), "1.0 MiB");
+    assert_eq!(format_si!(1048525), "1.0 MiB");
+    assert_eq!(format_si!(1048524), "1023.9 KiB");
+    assert_eq!(format_si!(1048576), "1.0 MiB");
+    assert_eq!(format_si!(3355443), "3.2 MiB");
+    assert_eq!(format_si!(1u64 << 40), "1.0 TiB");
+    assert_eq!(format_si!(u64::MAX), "16.0 EiB");
+
+    // decimal units
+    assert_eq!(format_si!(999, 1000), "999 B");
+    assert_eq!(format_si!(1000, 1000), "1.0 kB");
+    assert_eq!(format_si!(1023, 1000), "1.0 kB");
+    assert_eq!(format_si!(1024, 1000), "1.0 kB");
+    assert_eq!(format_si!(1048576, 1000), "1.0 MB");
+    assert_eq!(format_si!(999_999, 1000), "1.0 MB");
+    assert_eq!(format_si!(999_949, 1000), "999.9 kB");
+    assert_eq!(format_si!(2_500_000_000u64, 1000), "2.5 GB");
+}
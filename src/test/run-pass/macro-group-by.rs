@@ -0,0 +1,30 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+fn main() {
+    let by_parity = group_by!(vec![5, 2, 8, 1, 3, 6], |x| *x % 2);
+    assert_eq!(by_parity.len(), 2);
+    assert_eq!(by_parity[0], vec![2, 8, 6]);
+    assert_eq!(by_parity[1], vec![5, 1, 3]);
+
+    let words = ["apple", "bean", "avocado", "cherry", "banana", "apricot"];
+    let by_initial = group_by!(words.iter(), |w| w.char_at(0));
+    assert_eq!(by_initial.len(), 3);
+    assert_eq!(by_initial['a'], vec![&"apple", &"avocado", &"apricot"]);
+    assert_eq!(by_initial['b'], vec![&"bean", &"banana"]);
+    assert_eq!(by_initial['c'], vec![&"cherry"]);
+
+    // owned keys
+    let by_len = group_by!(words.iter().cloned(), |w| w.len().to_string());
+    assert_eq!(by_len.get("6"), Some(&vec!["cherry", "banana"]));
+
+    let empty = group_by!(Vec::<u8>::new(), |x| *x);
+    assert!(empty.is_empty());
+}
@@ -0,0 +1,43 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![allow(unknown_features)]
+#![feature(box_syntax)]
+
+use std::old_io::{ChanReader, ChanWriter, stdio};
+use std::sync::mpsc::channel;
+use std::thread::Thread;
+
+fn main() {
+    let (tx, rx) = channel();
+    let (mut r, w) = (ChanReader::new(rx), ChanWriter::new(tx));
+    let _t = Thread::spawn(move|| {
+        stdio::set_stderr(box w);
+        let mut buf = vec![0x00u8, 0x01];
+        buf.push_all(b"ABCDEFGHIJ");
+        buf.push_all(&[0x7f, 0x80, 0xff]);
+        buf.push_all(b" xyz\n");
+        assert_eq!(buf.len(), 20);
+        hexdump!(buf);
+        // the buffer is only borrowed
+        assert_eq!(buf.len(), 20);
+
+        hexdump!(b"");
+        hexdump!(&buf[..16]);
+    });
+
+    let out = r.read_to_string().unwrap();
+    let lines = out.lines().collect::<Vec<_>>();
+    assert_eq!(lines, vec![
+        "00000000  00 01 41 42 43 44 45 46  47 48 49 4a 7f 80 ff 20  |..ABCDEFGHIJ... |",
+        "00000010  78 79 7a 0a                                       |xyz.|",
+        "00000000  00 01 41 42 43 44 45 46  47 48 49 4a 7f 80 ff 20  |..ABCDEFGHIJ... |",
+    ]);
+}
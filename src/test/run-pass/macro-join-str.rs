@@ -0,0 +1,49 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::cell::Cell;
+use std::fmt;
+
+// Displays the number of times it has been displayed.
+struct Counted(Cell<u32>);
+
+impl fmt::Display for Counted {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.set(self.0.get() + 1);
+        write!(f, "{}", self.0.get())
+    }
+}
+
+fn main() {
+    // a single argument has no separator
+    let s = join_str!(" -> ", "start");
+    assert_eq!(s, "start");
+    assert_eq!(s.capacity(), s.len());
+
+    // the lengths of the separator and of string-like arguments are reserved
+    // up front, but other arguments are only measured as they are written
+    let s = join_str!(" -> ", "start", 2);
+    assert_eq!(s, "start -> 2");
+    assert!(s.capacity() >= s.len());
+
+    let owned = "end".to_string();
+    let s = join_str!(" -> ", "start", 'ü', owned, &owned[1..],);
+    assert_eq!(s, "start -> ü -> end -> nd");
+    assert_eq!(s.capacity(), s.len());
+    // the arguments are only borrowed
+    assert_eq!(owned, "end");
+
+    assert_eq!(join_str!("", 1, 2, 3), "123");
+
+    // each argument is evaluated and formatted once
+    let c = Counted(Cell::new(0));
+    assert_eq!(join_str!(", ", c, c, c), "1, 2, 3");
+    assert_eq!(c.0.get(), 3);
+}
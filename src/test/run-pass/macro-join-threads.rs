@@ -0,0 +1,43 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::boxed::BoxAny;
+use std::old_io::timer;
+use std::sync::atomic::{AtomicBool, Ordering, ATOMIC_BOOL_INIT};
+use std::time::Duration;
+
+static FINISHED: AtomicBool = ATOMIC_BOOL_INIT;
+
+fn main() {
+    let words = vec!["a", "bb", "ccc"];
+    let (n, s, v) = join_threads!([
+        || 6u32 * 7,
+        || words.connect("-"),
+        || words.iter().map(|w| w.len()).collect::<Vec<_>>(),
+    ]);
+    assert_eq!(n.ok(), Some(42));
+    assert_eq!(s.ok(), Some("a-bb-ccc".to_string()));
+    assert_eq!(v.ok(), Some(vec![1, 2, 3]));
+
+    // a panic is reported in its own slot, and the other threads still run
+    // to completion
+    let (a, b) = join_threads!([
+        || -> () { panic!("first thread failed") },
+        || {
+            timer::sleep(Duration::milliseconds(50));
+            FINISHED.store(true, Ordering::SeqCst);
+            "done"
+        }
+    ]);
+    let msg = a.err().unwrap().downcast::<&'static str>().ok().unwrap();
+    assert_eq!(*msg, "first thread failed");
+    assert_eq!(b.ok(), Some("done"));
+    assert!(FINISHED.load(Ordering::SeqCst));
+}
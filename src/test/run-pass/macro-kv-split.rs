@@ -0,0 +1,33 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+fn main() {
+    assert_eq!(kv_split!("key=value", '='), Some(("key", "value")));
+    assert_eq!(kv_split!("key = value", " = "), Some(("key", "value")));
+
+    // only the first delimiter splits
+    assert_eq!(kv_split!("url=http://x/?a=b", '='), Some(("url", "http://x/?a=b")));
+    assert_eq!(kv_split!("a::b::c", "::"), Some(("a", "b::c")));
+
+    // empty parts
+    assert_eq!(kv_split!("=v", '='), Some(("", "v")));
+    assert_eq!(kv_split!("k=", '='), Some(("k", "")));
+
+    // no delimiter
+    assert_eq!(kv_split!("novalue", '='), None);
+    assert_eq!(kv_split!("a:b", "::"), None);
+    assert_eq!(kv_split!("", '='), None);
+
+    // multibyte delimiters, and a borrowed String
+    assert_eq!(kv_split!("α→β", '→'), Some(("α", "β")));
+    let line = "x: 1".to_string();
+    let (k, v) = kv_split!(line, ": ").unwrap();
+    assert_eq!((k, v), ("x", "1"));
+}
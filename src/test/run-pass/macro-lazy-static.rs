@@ -0,0 +1,59 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::collections::HashMap;
+use std::old_io::timer;
+use std::sync::atomic::{AtomicUsize, Ordering, ATOMIC_USIZE_INIT};
+use std::thread::Thread;
+use std::time::Duration;
+
+static INITS: AtomicUsize = ATOMIC_USIZE_INIT;
+
+fn slow_init() -> Vec<u32> {
+    INITS.fetch_add(1, Ordering::SeqCst);
+    // give the other threads plenty of time to pile up behind us
+    timer::sleep(Duration::milliseconds(50));
+    vec![1, 2, 3]
+}
+
+lazy_static! {
+    static ref NUMBERS: Vec<u32> = slow_init();
+
+    /// A public one, with a doc comment.
+    pub static ref NAMES: HashMap<u32, &'static str> = {
+        let mut m = HashMap::new();
+        m.insert(1, "one");
+        m
+    };
+}
+
+mod inner {
+    lazy_static!(pub static ref GREETING: String = "hello".to_string(););
+}
+
+fn main() {
+    // nothing is initialized until first use
+    assert_eq!(INITS.load(Ordering::SeqCst), 0);
+
+    let threads = (0..8).map(|_| {
+        Thread::scoped(|| NUMBERS.iter().fold(0, |a, &b| a + b))
+    }).collect::<Vec<_>>();
+    for t in threads {
+        assert_eq!(t.join().ok(), Some(6));
+    }
+    assert_eq!(*NUMBERS, vec![1, 2, 3]);
+    assert_eq!(INITS.load(Ordering::SeqCst), 1);
+
+    // every access sees the same value
+    assert_eq!(&*NUMBERS as *const Vec<u32>, &*NUMBERS as *const Vec<u32>);
+
+    assert_eq!(NAMES.get(&1), Some(&"one"));
+    assert_eq!(*inner::GREETING, "hello");
+}
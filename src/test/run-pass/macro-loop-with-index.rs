@@ -0,0 +1,58 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+fn main() {
+    let mut indices = vec![];
+    loop_with_index!(i in vec!["a", "b", "c"] => {
+        indices.push(i);
+    });
+    assert_eq!(indices, vec![0, 1, 2]);
+
+    let mut pairs = vec![];
+    loop_with_index!((i, x) in [10, 20, 30].iter() => {
+        pairs.push((i, *x));
+    });
+    assert_eq!(pairs, vec![(0, 10), (1, 20), (2, 30)]);
+
+    // `break` stops early
+    let mut last = None;
+    loop_with_index!((i, x) in 0..100 => {
+        if x * x > 10 {
+            break;
+        }
+        last = Some(i);
+    });
+    assert_eq!(last, Some(3));
+
+    // `continue` still advances the counter
+    let mut odd = vec![];
+    loop_with_index!((i, x) in "abcdef".chars() => {
+        if i % 2 == 0 {
+            continue;
+        }
+        odd.push(x);
+    });
+    assert_eq!(odd, vec!['b', 'd', 'f']);
+
+    // the body may change the counter, and counting carries on from there
+    let mut stepped = vec![];
+    loop_with_index!(i in 0..6 => {
+        stepped.push(i);
+        i += 1;
+    });
+    assert_eq!(stepped, vec![0, 2, 4, 6, 8, 10]);
+
+    // patterns can destructure elements
+    let mut sums = vec![];
+    loop_with_index!((i, (a, b)) in vec![(1, 2), (3, 4)] => {
+        sums.push(i + a + b);
+    });
+    assert_eq!(sums, vec![3, 8]);
+}
@@ -0,0 +1,33 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::char;
+use std::collections::BTreeSet;
+
+fn main() {
+    // into a Vec, which is presized
+    let v = map_collect!(vec![1, 22, 333], |x| x.to_string());
+    assert_eq!(v, vec!["1".to_string(), "22".to_string(), "333".to_string()]);
+    assert!(v.capacity() >= 3);
+
+    let lens = map_collect!(v.iter(), |s| s.len());
+    assert_eq!(lens, vec![1, 2, 3]);
+
+    let empty = map_collect!(Vec::<u8>::new(), |x| x + 1);
+    assert!(empty.is_empty());
+
+    // into a String, via chars
+    let digits = map_collect!(0..5us, |d| char::from_digit(d, 10).unwrap() => String);
+    assert_eq!(digits, "01234");
+
+    // or any other FromIterator
+    let set = map_collect!(vec![3, 1, 3, 2], |x| x * 10 => BTreeSet<i32>);
+    assert_eq!(set.into_iter().collect::<Vec<_>>(), vec![10, 20, 30]);
+}
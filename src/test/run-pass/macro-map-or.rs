@@ -0,0 +1,48 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::cell::Cell;
+
+fn fallback(calls: &Cell<u32>, n: usize) -> usize {
+    calls.set(calls.get() + 1);
+    n
+}
+
+fn double(x: u32) -> u32 { x * 2 }
+
+fn main() {
+    let calls = Cell::new(0);
+
+    // Some: the mapping is applied, and the default is never evaluated
+    let v = map_or!(Some("four"), fallback(&calls, 0), |s: &str| s.len());
+    assert_eq!(v, 4);
+    assert_eq!(calls.get(), 0);
+
+    // None: the default is used, and evaluated once
+    let none: Option<&str> = None;
+    let v = map_or!(none, fallback(&calls, 7), |s: &str| s.len());
+    assert_eq!(v, 7);
+    assert_eq!(calls.get(), 1);
+
+    // any function will do, and the option may be moved in
+    assert_eq!(map_or!(Some(21), 0, double), 42);
+    let name = Some("owned".to_string());
+    assert_eq!(map_or!(name.as_ref(), 0, |s: &String| s.len()), 5);
+    assert_eq!(map_or!(name, String::new(), |s: String| s + "!"), "owned!");
+
+    // map_or_else! calls the default closure only for None
+    calls.set(0);
+    let v = map_or_else!(Some(3), || fallback(&calls, 0) as u32, double);
+    assert_eq!(v, 6);
+    assert_eq!(calls.get(), 0);
+    let v = map_or_else!(None, || fallback(&calls, 9) as u32, double);
+    assert_eq!(v, 9);
+    assert_eq!(calls.get(), 1);
+}
@@ -0,0 +1,35 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+newtype!(pub struct Meters(f64): Display, Deref, From;);
+
+newtype!(
+    #[derive(Clone, PartialEq)]
+    struct Names(Vec<String>): Debug, Deref, DerefMut, From;
+);
+
+newtype!(struct Plain(u8););
+
+fn main() {
+    let m = Meters::from(2.5);
+    assert_eq!(format!("{}", m), "2.5");
+    assert_eq!(format!("{:.2}", m), "2.50");
+    assert_eq!(*m + 1.0, 3.5);
+    assert!(m.is_sign_positive());
+
+    let mut names = Names::from(vec!["a".to_string()]);
+    names.push("b".to_string());
+    assert_eq!(names.len(), 2);
+    assert_eq!(format!("{:?}", names), format!("{:?}", vec!["a", "b"]));
+    assert!(names.clone() == names);
+
+    let Plain(x) = Plain(7);
+    assert_eq!(x, 7);
+}
@@ -0,0 +1,27 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+fn main() {
+    let items = vec!["Open", "Save", "Quit"];
+    assert_eq!(numbered_list!(&items), "1. Open\n2. Save\n3. Quit");
+    assert_eq!(numbered_list!(vec![2.5f64, -0.5]), "1. 2.5\n2. -0.5");
+
+    // a different starting number
+    assert_eq!(numbered_list!(&items, from: 0), "0. Open\n1. Save\n2. Quit");
+    let start = 9;
+    assert_eq!(numbered_list!(items.iter().take(2), from: start),
+               "9. Open\n10. Save");
+
+    // empty lists give empty strings
+    let empty: Vec<&str> = vec![];
+    assert_eq!(numbered_list!(&empty), "");
+    assert_eq!(numbered_list!(empty, from: 5), "");
+    assert_eq!(numbered_list!(Some('x').into_iter()), "1. x");
+}
@@ -0,0 +1,33 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::cell::Cell;
+
+fn main() {
+    assert_eq!(or_default!(Some(3)), 3);
+    assert_eq!(or_default!(Some("x".to_string()), String), "x");
+
+    // the default is inferred from the option's type
+    let none: Option<Vec<u8>> = None;
+    assert_eq!(or_default!(none), vec![]);
+    let n: u64 = or_default!(None);
+    assert_eq!(n, 0);
+
+    // or named explicitly
+    assert_eq!(or_default!(None, i32), 0);
+    assert_eq!(or_default!("12".parse().ok(), u8), 12);
+    assert_eq!(or_default!("-1".parse().ok(), u8), 0);
+
+    // the option is evaluated once
+    let calls = Cell::new(0);
+    let next = || { calls.set(calls.get() + 1); Some(calls.get()) };
+    assert_eq!(or_default!(next()), 1);
+    assert_eq!(calls.get(), 1);
+}
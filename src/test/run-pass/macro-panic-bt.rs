@@ -0,0 +1,32 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::boxed::BoxAny;
+use std::rt::backtrace::PanicBacktrace;
+use std::thread::Thread;
+
+fn payload<F: FnOnce() + Send>(f: F) -> Box<PanicBacktrace> {
+    let err = Thread::scoped(f).join().err().unwrap();
+    err.downcast::<PanicBacktrace>().ok().unwrap()
+}
+
+fn main() {
+    let p = payload(|| panic_bt!("bad value: {}", 3));
+    assert_eq!(p.msg, "bad value: 3");
+    // The backtrace may be empty where traces aren't supported, but if one
+    // was taken it should look like the one printed with RUST_BACKTRACE.
+    assert!(p.backtrace.is_empty() || p.backtrace.starts_with("stack backtrace:"));
+
+    let p = payload(|| panic_bt!("no arguments"));
+    assert_eq!(p.msg, "no arguments");
+
+    let p = payload(|| panic_bt!());
+    assert_eq!(p.msg, "explicit panic");
+}
@@ -0,0 +1,57 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::cell::Cell;
+use std::cmp::Ordering::{self, Less, Equal, Greater};
+use std::f64::NAN;
+
+fn counted(counter: &Cell<u32>, a: f64, b: f64) -> Option<Ordering> {
+    counter.set(counter.get() + 1);
+    a.partial_cmp(&b)
+}
+
+#[derive(PartialEq)]
+struct Sample { weight: f64, height: f64 }
+
+impl PartialOrd for Sample {
+    fn partial_cmp(&self, other: &Sample) -> Option<Ordering> {
+        partial_cmp_chain!(self.weight.partial_cmp(&other.weight),
+                           self.height.partial_cmp(&other.height))
+    }
+}
+
+fn main() {
+    let n = Cell::new(0);
+
+    // an incomparable key gives None, and stops there
+    assert_eq!(partial_cmp_chain!(counted(&n, 1.0, 1.0), counted(&n, NAN, 2.0),
+                                  counted(&n, 1.0, 2.0)),
+               None);
+    assert_eq!(n.get(), 2);
+
+    // a deciding key
+    n.set(0);
+    assert_eq!(partial_cmp_chain!(counted(&n, 2.0, 1.0), counted(&n, NAN, 2.0)),
+               Some(Greater));
+    assert_eq!(n.get(), 1);
+
+    // everything equal
+    n.set(0);
+    assert_eq!(partial_cmp_chain!(counted(&n, 1.0, 1.0), counted(&n, -0.0, 0.0),),
+               Some(Equal));
+    assert_eq!(n.get(), 2);
+
+    let a = Sample { weight: 1.0, height: 2.0 };
+    let b = Sample { weight: 1.0, height: 3.0 };
+    let c = Sample { weight: 1.0, height: NAN };
+    assert!(a < b);
+    assert_eq!(a.partial_cmp(&b), Some(Less));
+    assert_eq!(a.partial_cmp(&c), None);
+}
@@ -0,0 +1,36 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::path::{Path, PathBuf};
+
+fn main() {
+    let home = String::from_str("/home");
+    let user = "alice";
+    let p = path!(home, user, Path::new("docs"), "notes.txt");
+    assert_eq!(p, Path::new("/home").join("alice").join("docs").join("notes.txt"));
+    assert!(p.has_root());
+    assert_eq!(p.file_name().and_then(|s| s.to_str()), Some("notes.txt"));
+    // the components were only borrowed
+    assert_eq!(home, "/home");
+
+    let base = PathBuf::new("target");
+    let p = path!(base, "debug", String::from_str("build"),);
+    assert_eq!(p, Path::new("target").join("debug").join("build"));
+    assert!(p.is_relative());
+
+    assert_eq!(path!("single"), PathBuf::new("single"));
+
+    // an absolute component replaces what came before it
+    assert_eq!(path!("a", "/b", "c"), Path::new("/b").join("c"));
+
+    if cfg!(unix) {
+        assert_eq!(path!("/usr", "local", "bin").to_str(), Some("/usr/local/bin"));
+    }
+}
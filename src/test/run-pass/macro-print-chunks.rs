@@ -0,0 +1,41 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![allow(unknown_features)]
+#![feature(box_syntax)]
+
+use std::old_io::{ChanReader, ChanWriter, stdio};
+use std::sync::mpsc::channel;
+use std::thread::Thread;
+
+fn main() {
+    let (tx, rx) = channel();
+    let (mut r, w) = (ChanReader::new(rx), ChanWriter::new(tx));
+    let _t = Thread::spawn(move|| {
+        stdio::set_stderr(box w);
+        let v = (0..20).collect::<Vec<u8>>();
+        print_chunks!(v, 8);
+        // the slice is only borrowed
+        assert_eq!(v.len(), 20);
+
+        print_chunks!(["a", "b"], 2);
+        let empty: [i32; 0] = [];
+        print_chunks!(empty, 3);
+    });
+
+    let out = r.read_to_string().unwrap();
+    let lines = out.lines().collect::<Vec<_>>();
+    assert_eq!(lines, vec![
+        " 0: [0, 1, 2, 3, 4, 5, 6, 7]",
+        " 8: [8, 9, 10, 11, 12, 13, 14, 15]",
+        "16: [16, 17, 18, 19]",
+        "0: [\"a\", \"b\"]",
+    ]);
+}
@@ -0,0 +1,42 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![allow(unknown_features)]
+#![feature(box_syntax)]
+
+use std::old_io::{ChanReader, ChanWriter, stdio};
+use std::sync::mpsc::channel;
+use std::thread::Thread;
+
+fn main() {
+    let (tx, rx) = channel();
+    let (mut r, w) = (ChanReader::new(rx), ChanWriter::new(tx));
+    let _t = Thread::spawn(move|| {
+        stdio::set_stderr(box w);
+        let host = String::from_str("example.com");
+        let port = 443;
+        print_kv!{ "host" => host, "port" => port, "timeout secs" => 2.5f64 };
+        // the values are only borrowed
+        assert_eq!(host, "example.com");
+
+        print_kv!{ 'k' => "v", "ключ" => true, };
+        print_kv!{};
+    });
+
+    let out = r.read_to_string().unwrap();
+    let lines = out.lines().collect::<Vec<_>>();
+    assert_eq!(lines, vec![
+        "host:         example.com",
+        "port:         443",
+        "timeout secs: 2.5",
+        "k:    v",
+        "ключ: true",
+    ]);
+}
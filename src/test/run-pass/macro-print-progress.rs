@@ -0,0 +1,56 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![allow(unknown_features)]
+#![feature(box_syntax)]
+
+use std::env;
+use std::old_io::{ChanReader, ChanWriter, stdio};
+use std::sync::mpsc::channel;
+use std::thread::Thread;
+
+fn capture<F: FnOnce() + Send + 'static>(f: F) -> String {
+    let (tx, rx) = channel();
+    let (mut r, w) = (ChanReader::new(rx), ChanWriter::new(tx));
+    let _t = Thread::spawn(move|| {
+        stdio::set_stdout(box w);
+        f();
+    });
+    r.read_to_string().unwrap()
+}
+
+fn main() {
+    // a replaced stdout isn't a terminal, so nothing is printed
+    env::remove_var("CLICOLOR_FORCE");
+    let out = capture(|| {
+        print_progress!(1, 2);
+        print_progress_done!();
+    });
+    assert_eq!(out, "");
+
+    // unless it's forced to be treated as one
+    env::set_var("CLICOLOR_FORCE", "1");
+    let out = capture(|| {
+        for i in 0..3 {
+            print_progress!(i, 2u8);
+        }
+        print_progress_done!();
+    });
+    assert_eq!(out, concat!("\r[                    ]   0% (0/2)",
+                            "\r[##########          ]  50% (1/2)",
+                            "\r[####################] 100% (2/2)",
+                            "\n"));
+
+    let out = capture(|| {
+        print_progress!(1, 3);
+        print_progress!(0, 0);
+    });
+    assert_eq!(out, "\r[######              ]  33% (1/3)\r[####################] 100% (0/0)");
+}
@@ -0,0 +1,48 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![allow(unknown_features, dead_code)]
+#![feature(box_syntax)]
+
+use std::mem;
+use std::old_io::{ChanReader, ChanWriter, stdio};
+use std::sync::mpsc::channel;
+use std::thread::Thread;
+
+struct Pair {
+    a: u8,
+    b: u32,
+}
+
+enum Shape {
+    Dot,
+    Circle(f64),
+}
+
+fn main() {
+    let (tx, rx) = channel();
+    let (mut r, w) = (ChanReader::new(rx), ChanWriter::new(tx));
+    let _t = Thread::spawn(move|| {
+        stdio::set_stderr(box w);
+        print_type_sizes!(u16, Pair, Shape,);
+    });
+
+    let out = r.read_to_string().unwrap();
+    let lines = out.lines().map(|l| l.to_string()).collect::<Vec<_>>();
+    assert_eq!(lines, vec![
+        format!("u16: size {}, align {}",
+                mem::size_of::<u16>(), mem::align_of::<u16>()),
+        format!("Pair: size {}, align {}",
+                mem::size_of::<Pair>(), mem::align_of::<Pair>()),
+        format!("Shape: size {}, align {}",
+                mem::size_of::<Shape>(), mem::align_of::<Shape>()),
+    ]);
+    assert_eq!(mem::size_of::<u16>(), 2);
+}
@@ -0,0 +1,42 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![allow(unknown_features)]
+#![feature(box_syntax)]
+
+use std::old_io::{ChanReader, ChanWriter, stdio};
+use std::sync::mpsc::channel;
+use std::thread::Thread;
+
+fn main() {
+    let (tx, rx) = channel();
+    let (mut r, w) = (ChanReader::new(rx), ChanWriter::new(tx));
+    let _t = Thread::spawn(move|| {
+        stdio::set_stderr(box w);
+        let name = "widget".to_string();
+        let count = 3;
+        println_json!{ "name": name, "count": count };
+        // the values are only borrowed
+        assert_eq!(name, "widget");
+
+        println_json!{ "ok": true, "ratio": 0.5f64, "tag": 'x', };
+        println_json!{ "quote": "say \"hi\"" };
+        println_json!{};
+    });
+
+    let out = r.read_to_string().unwrap();
+    let lines = out.lines().collect::<Vec<_>>();
+    assert_eq!(lines, vec![
+        r#"{"name": "widget", "count": 3}"#,
+        r#"{"ok": true, "ratio": 0.5, "tag": 'x'}"#,
+        r#"{"quote": "say \"hi\""}"#,
+        "{}",
+    ]);
+}
@@ -0,0 +1,53 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[derive(Debug)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Debug)]
+struct Shape {
+    name: String,
+    points: Vec<Point>,
+    closed: bool,
+}
+
+fn into_string(shape: &Shape) -> String {
+    use std::fmt::Writer;
+
+    let mut s = String::new();
+    assert!(pwrite!(&mut s, shape).is_ok());
+    s
+}
+
+fn into_bytes(shape: &Shape) -> Vec<u8> {
+    // after what is already there
+    let mut w = b"shape: ".to_vec();
+    assert!(pwrite!(&mut w, shape).is_ok());
+    assert!(w.write_str("\n").is_ok());
+    w
+}
+
+fn main() {
+    let shape = Shape {
+        name: "triangle".to_string(),
+        points: vec![Point { x: 0, y: 0 }, Point { x: 4, y: 0 }, Point { x: 0, y: 3 }],
+        closed: true,
+    };
+    let expected = format!("{:#?}", shape);
+
+    // into a fmt::Writer
+    assert_eq!(into_string(&shape), expected);
+
+    // into an old_io::Writer
+    assert_eq!(into_bytes(&shape), format!("shape: {}\n", expected).into_bytes());
+}
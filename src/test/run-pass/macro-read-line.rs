@@ -0,0 +1,36 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![allow(unknown_features)]
+#![feature(box_syntax)]
+
+use std::old_io::{ChanReader, ChanWriter, EndOfFile, MemReader, stdio};
+use std::sync::mpsc::channel;
+use std::thread::Thread;
+
+fn main() {
+    let (tx, rx) = channel();
+    let (mut r, w) = (ChanReader::new(rx), ChanWriter::new(tx));
+    let t = Thread::scoped(move|| {
+        stdio::set_stdout(box w);
+        let input = b"Alice\nBob\r\n\nlast".to_vec();
+        assert!(stdio::set_stdin(box MemReader::new(input)).is_none());
+
+        assert_eq!(read_line!().unwrap(), "Alice");
+        assert_eq!(read_line!(prompt: "Enter name: ").unwrap(), "Bob");
+        assert_eq!(read_line!(prompt: "{}> ", 2).unwrap(), "");
+        // the final line has no newline to remove
+        assert_eq!(read_line!().unwrap(), "last");
+        assert_eq!(read_line!().err().unwrap().kind, EndOfFile);
+    });
+    assert!(t.join().is_ok());
+
+    assert_eq!(r.read_to_string().unwrap(), "Enter name: 2> ");
+}
@@ -0,0 +1,35 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::old_io::timer;
+use std::sync::mpsc::{channel, RecvError};
+use std::thread::Thread;
+use std::time::Duration;
+
+fn main() {
+    // a message which arrives in time
+    let (tx, rx) = channel();
+    let _t = Thread::spawn(move|| {
+        timer::sleep(Duration::milliseconds(20));
+        tx.send("hello").unwrap();
+    });
+    assert_eq!(recv_timeout!(rx, Duration::seconds(10)), Ok(Some("hello")));
+
+    // a timeout, with the sender still around
+    let (tx, rx) = channel::<i32>();
+    assert_eq!(recv_timeout!(rx, Duration::milliseconds(20)), Ok(None));
+
+    // messages already sent are still received after a hang up, and then the
+    // disconnection is reported
+    tx.send(5).unwrap();
+    drop(tx);
+    assert_eq!(recv_timeout!(rx, Duration::seconds(10)), Ok(Some(5)));
+    assert_eq!(recv_timeout!(rx, Duration::seconds(10)), Err(RecvError));
+}
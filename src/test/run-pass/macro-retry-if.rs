@@ -0,0 +1,64 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::cell::Cell;
+use std::time::Duration;
+
+#[derive(Debug, PartialEq)]
+enum Error { Busy, Denied }
+
+// Fails with each of `errors` in turn, and then succeeds.
+fn flaky(attempts: &Cell<usize>, errors: &[Error]) -> Result<usize, Error> {
+    let n = attempts.get();
+    attempts.set(n + 1);
+    match errors.get(n) {
+        Some(&Error::Busy) => Err(Error::Busy),
+        Some(&Error::Denied) => Err(Error::Denied),
+        None => Ok(n + 1),
+    }
+}
+
+fn main() {
+    let retryable = |e: &Error| *e == Error::Busy;
+
+    // retryable errors are retried until success
+    let attempts = Cell::new(0);
+    let r = retry_if!(5, retryable, flaky(&attempts, &[Error::Busy, Error::Busy]));
+    assert_eq!(r, Ok(3));
+    assert_eq!(attempts.get(), 3);
+
+    // other errors are returned at once
+    attempts.set(0);
+    let r = retry_if!(5, retryable, flaky(&attempts, &[Error::Denied]));
+    assert_eq!(r, Err(Error::Denied));
+    assert_eq!(attempts.get(), 1);
+
+    attempts.set(0);
+    let r = retry_if!(5, retryable, flaky(&attempts, &[Error::Busy, Error::Denied]));
+    assert_eq!(r, Err(Error::Denied));
+    assert_eq!(attempts.get(), 2);
+
+    // the last attempt's result is given, whatever it is
+    attempts.set(0);
+    let r = retry_if!(2, retryable, flaky(&attempts, &[Error::Busy, Error::Busy]));
+    assert_eq!(r, Err(Error::Busy));
+    assert_eq!(attempts.get(), 2);
+
+    // there is always at least one attempt
+    attempts.set(0);
+    let r = retry_if!(0, retryable, flaky(&attempts, &[]));
+    assert_eq!(r, Ok(1));
+
+    // with a pause between attempts
+    attempts.set(0);
+    let r = retry_if!(3, |_: &Error| true, flaky(&attempts, &[Error::Denied]),
+                      Duration::milliseconds(1));
+    assert_eq!(r, Ok(2));
+}
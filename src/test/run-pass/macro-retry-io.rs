@@ -0,0 +1,67 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::cell::Cell;
+use std::io::{self, Error, ErrorKind};
+use std::old_io::{self, IoError, IoErrorKind, IoResult};
+
+// Fails with `Interrupted` the first `failures` times it's called, and then
+// gives `last`.
+fn interrupted_then(attempts: &Cell<u32>, failures: u32, last: Result<u32, ErrorKind>)
+                    -> io::Result<u32> {
+    attempts.set(attempts.get() + 1);
+    if attempts.get() <= failures {
+        Err(Error::new(ErrorKind::Interrupted, "interrupted", None))
+    } else {
+        last.map_err(|kind| Error::new(kind, "failed", None))
+    }
+}
+
+// The same, for the old I/O error type.
+fn old_interrupted_then(attempts: &Cell<u32>, failures: u32, last: Result<u32, IoErrorKind>)
+                        -> IoResult<u32> {
+    attempts.set(attempts.get() + 1);
+    if attempts.get() <= failures {
+        Err(old_io::standard_error(old_io::Interrupted))
+    } else {
+        last.map_err(|kind| IoError { kind: kind, desc: "failed", detail: None })
+    }
+}
+
+fn main() {
+    let attempts = Cell::new(0);
+    let r = retry_io!(interrupted_then(&attempts, 2, Ok(7)));
+    assert_eq!(r.ok(), Some(7));
+    assert_eq!(attempts.get(), 3);
+
+    // other errors are returned at once
+    attempts.set(0);
+    let denied = Err(ErrorKind::PermissionDenied);
+    let r = retry_io!(interrupted_then(&attempts, 0, denied));
+    assert_eq!(r.err().map(|e| e.kind()), Some(ErrorKind::PermissionDenied));
+    assert_eq!(attempts.get(), 1);
+
+    // including after some interruptions
+    attempts.set(0);
+    let r = retry_io!(interrupted_then(&attempts, 5, denied));
+    assert_eq!(r.err().map(|e| e.kind()), Some(ErrorKind::PermissionDenied));
+    assert_eq!(attempts.get(), 6);
+
+    // old_io results work the same way
+    attempts.set(0);
+    let r = retry_io!(old_interrupted_then(&attempts, 3, Ok(9)));
+    assert_eq!(r.ok(), Some(9));
+    assert_eq!(attempts.get(), 4);
+
+    attempts.set(0);
+    let r = retry_io!(old_interrupted_then(&attempts, 1, Err(old_io::EndOfFile)));
+    assert_eq!(r.err().map(|e| e.kind), Some(old_io::EndOfFile));
+    assert_eq!(attempts.get(), 2);
+}
@@ -0,0 +1,40 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::cell::Cell;
+use std::time::Duration;
+
+fn main() {
+    // becomes true on the third poll
+    let polls = Cell::new(0);
+    let ok = retry_until!(Duration::seconds(10), Duration::milliseconds(1), || {
+        polls.set(polls.get() + 1);
+        polls.get() == 3
+    });
+    assert!(ok);
+    assert_eq!(polls.get(), 3);
+
+    // true straight away, so there's no waiting
+    let polls = Cell::new(0);
+    assert!(retry_until!(Duration::zero(), Duration::seconds(10), || {
+        polls.set(polls.get() + 1);
+        true
+    }));
+    assert_eq!(polls.get(), 1);
+
+    // never true: give up after the timeout, having polled a few times
+    let polls = Cell::new(0);
+    let ok = retry_until!(Duration::milliseconds(50), Duration::milliseconds(10), || {
+        polls.set(polls.get() + 1);
+        false
+    });
+    assert!(!ok);
+    assert!(polls.get() >= 2);
+}
@@ -0,0 +1,70 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![allow(unknown_features)]
+#![feature(box_syntax)]
+
+use std::old_io::{ChanReader, ChanWriter, stdio};
+use std::sync::mpsc::channel;
+use std::thread::Thread;
+
+fn log_event(n: u32) {
+    ring_log!(2, "event {}", n);
+}
+
+fn dumped() -> Vec<String> {
+    let (tx, rx) = channel();
+    let (mut r, w) = (ChanReader::new(rx), ChanWriter::new(tx));
+    let _t = Thread::spawn(move|| {
+        stdio::set_stderr(box w);
+        dump_ring_log!();
+    });
+    let out = r.read_to_string().unwrap();
+    out.lines().map(|l| l.to_string()).collect()
+}
+
+fn main() {
+    // nothing has been logged yet
+    assert!(dumped().is_empty());
+
+    // only the most recent messages of each call site are kept, and all of
+    // them are dumped in the order they were logged
+    for i in 0..10 {
+        ring_log!(3, "step {}", i);
+        if i % 4 == 0 {
+            log_event(i);
+        }
+    }
+    assert_eq!(dumped(), vec!["event 4", "step 7", "step 8", "event 8", "step 9"]);
+
+    // dumping leaves the messages where they are
+    assert_eq!(dumped().len(), 5);
+
+    // a call site is shared between threads
+    let guards = (0..4).map(|t| Thread::scoped(move|| {
+        for i in 0..25 {
+            ring_log!(1000, "thread {} message {}", t, i);
+        }
+    })).collect::<Vec<_>>();
+    for g in guards.into_iter() {
+        assert!(g.join().is_ok());
+    }
+    let lines = dumped();
+    assert_eq!(lines.len(), 105);
+    for t in 0..4 {
+        let own = lines.iter()
+                       .filter(|l| l.starts_with(&format!("thread {} ", t)[]))
+                       .map(|l| l.clone())
+                       .collect::<Vec<_>>();
+        let expected = (0..25).map(|i| format!("thread {} message {}", t, i))
+                              .collect::<Vec<_>>();
+        assert_eq!(own, expected);
+    }
+}
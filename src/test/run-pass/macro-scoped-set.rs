@@ -0,0 +1,60 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::thread::Thread;
+
+struct Config {
+    level: u32,
+    name: String,
+}
+
+static mut LIMIT: usize = 10;
+
+fn limit() -> usize {
+    unsafe { LIMIT }
+}
+
+fn main() {
+    // a local
+    let mut x = 1;
+    let r = scoped_set!(x, 2, {
+        assert_eq!(x, 2);
+        x += 1;
+        x * 10
+    });
+    assert_eq!(r, 30);
+    assert_eq!(x, 1);
+
+    // struct fields, including ones which aren't Copy
+    let mut config = Config { level: 1, name: "default".to_string() };
+    scoped_set!(config.name, "override".to_string(), {
+        assert_eq!(config.name, "override");
+        assert_eq!(config.level, 1);
+    });
+    assert_eq!(config.name, "default");
+
+    // the old value comes back when the block panics
+    let result = Thread::scoped(|| {
+        scoped_set!(config.level, 5, {
+            assert_eq!(config.level, 5);
+            panic!("oops");
+        })
+    }).join();
+    assert!(result.is_err());
+    assert_eq!(config.level, 1);
+
+    // and statics can be overridden too
+    unsafe {
+        scoped_set!(LIMIT, 3, {
+            assert_eq!(limit(), 3);
+        });
+    }
+    assert_eq!(limit(), 10);
+}
@@ -0,0 +1,45 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::boxed::BoxAny;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread::Thread;
+
+fn sum(xs: &[u64]) -> u64 {
+    xs.iter().fold(0, |a, &b| a + b)
+}
+
+fn main() {
+    let data = (1..101).collect::<Vec<u64>>();
+    let (a, b, c, d) = scoped_tasks!([
+        || sum(&data[..25]),
+        || sum(&data[25..50]),
+        || sum(&data[50..75]),
+        || sum(&data[75..]),
+    ]);
+    assert_eq!((a, b, c, d), (325, 950, 1575, 2200));
+    assert_eq!(a + b + c + d, sum(&data[]));
+
+    let (only,) = scoped_tasks!([|| data.len()]);
+    assert_eq!(only, 100);
+
+    // a panic is resumed once every task has finished
+    let finished = AtomicUsize::new(0);
+    let res = Thread::scoped(|| {
+        scoped_tasks!([
+            || { finished.fetch_add(1, Ordering::SeqCst); },
+            || -> () { panic!("task failed") },
+            || { finished.fetch_add(1, Ordering::SeqCst); },
+        ]);
+    }).join();
+    assert_eq!(finished.load(Ordering::SeqCst), 2);
+    let payload = res.err().unwrap();
+    assert_eq!(*payload.downcast::<&'static str>().ok().unwrap(), "task failed");
+}
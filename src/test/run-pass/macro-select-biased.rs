@@ -0,0 +1,61 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::old_io::timer;
+use std::sync::mpsc::{channel, RecvError};
+use std::thread::Thread;
+use std::time::Duration;
+
+fn main() {
+    // with both ready, the first arm always wins
+    for _ in 0..100 {
+        let (tx1, rx1) = channel();
+        let (tx2, rx2) = channel();
+        tx2.send(2).unwrap();
+        tx1.send(1).unwrap();
+        let got = select_biased! {
+            n = rx1.recv() => ("first", n.unwrap()),
+            n = rx2.recv() => ("second", n.unwrap())
+        };
+        assert_eq!(got, ("first", 1));
+
+        // ...and the other value is still waiting
+        let got = select_biased! {
+            n = rx1.recv() => ("first", n.unwrap()),
+            n = rx2.recv() => ("second", n.unwrap())
+        };
+        assert_eq!(got, ("second", 2));
+    }
+
+    // a disconnected channel counts as ready
+    let (tx1, rx1) = channel::<i32>();
+    let (tx2, rx2) = channel::<i32>();
+    drop(tx1);
+    tx2.send(5).unwrap();
+    select_biased! {
+        r = rx1.recv() => assert_eq!(r, Err(RecvError)),
+        _r = rx2.recv() => panic!("the disconnected receiver comes first")
+    }
+
+    // with nothing ready, this blocks until a value arrives
+    let (tx1, rx1) = channel::<&'static str>();
+    let (tx2, rx2) = channel();
+    let t = Thread::scoped(move|| {
+        timer::sleep(Duration::milliseconds(50));
+        tx2.send("late").unwrap();
+    });
+    let got = select_biased! {
+        _m = rx1.recv() => panic!("nothing was sent on rx1"),
+        m = rx2.recv() => m.unwrap()
+    };
+    assert_eq!(got, "late");
+    assert!(t.join().is_ok());
+    drop(tx1);
+}
@@ -0,0 +1,41 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::old_io::timer;
+use std::time::Duration;
+
+fn main() {
+    // a deadline in the near future is slept until
+    let deadline = deadline!(Duration::milliseconds(50));
+    let elapsed = Duration::span(|| sleep_until!(deadline));
+    assert!(elapsed >= Duration::milliseconds(45), "woke after {}", elapsed);
+    assert!(elapsed < Duration::seconds(5), "woke after {}", elapsed);
+
+    // time spent before sleeping counts towards the deadline, which is
+    // reached either way
+    let mut deadline = deadline!(Duration::zero());
+    let elapsed = Duration::span(|| {
+        deadline = deadline!(Duration::milliseconds(100));
+        timer::sleep(Duration::milliseconds(60));
+        sleep_until!(deadline);
+    });
+    assert!(elapsed >= Duration::milliseconds(95), "woke after {}", elapsed);
+    assert!(deadline.remaining() <= Duration::zero());
+
+    // a deadline which has passed returns immediately, however often it is
+    // waited for
+    let past = deadline!(Duration::milliseconds(-100));
+    let elapsed = Duration::span(|| {
+        sleep_until!(past);
+        sleep_until!(past);
+    });
+    assert!(elapsed < Duration::seconds(5), "woke after {}", elapsed);
+    assert!(past.remaining() <= Duration::zero());
+}
@@ -0,0 +1,31 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::collections::HashSet;
+
+fn main() {
+    assert_eq!(sorted_vec!(vec![5, 3, 9, 1, 3]), vec![1, 3, 3, 5, 9]);
+    assert_eq!(sorted_vec!(vec![-1i64, 0, -7]), vec![-7, -1, 0]);
+    assert_eq!(sorted_vec!((0..4).rev()), vec![0, 1, 2, 3]);
+    let set = vec![4u8, 2, 8].into_iter().collect::<HashSet<_>>();
+    assert_eq!(sorted_vec!(set), vec![2, 4, 8]);
+    let empty: Vec<i32> = vec![];
+    assert!(sorted_vec!(empty).is_empty());
+
+    // the comparator form is a stable sort
+    let words = vec!["pear", "fig", "apple", "kiwi", "date"];
+    assert_eq!(sorted_vec_by!(words.iter().cloned(), |a, b| a.len().cmp(&b.len())),
+               vec!["fig", "pear", "kiwi", "date", "apple"]);
+    assert_eq!(sorted_vec_by!(words, |a, b| b.cmp(a)),
+               vec!["pear", "kiwi", "fig", "date", "apple"]);
+
+    assert_eq!(sorted_vec_desc!(vec![5, 3, 9, 1, 3]), vec![9, 5, 3, 3, 1]);
+    assert_eq!(sorted_vec_desc!("bca".chars()), vec!['c', 'b', 'a']);
+}
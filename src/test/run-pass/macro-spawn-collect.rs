@@ -0,0 +1,44 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::boxed::BoxAny;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread::Thread;
+
+fn main() {
+    // results come back in the order of the items
+    let inputs = vec![5u64, 1, 4, 2, 3];
+    let results = spawn_collect!(inputs.clone(), |n: u64| n * n);
+    let squares = results.into_iter().map(|r| r.ok().unwrap()).collect::<Vec<_>>();
+    assert_eq!(squares, vec![25, 1, 16, 4, 9]);
+
+    // the closure runs once per item, off the main thread, and may borrow
+    let calls = AtomicUsize::new(0);
+    let unnamed = spawn_collect!(inputs.iter(), |_: &u64| {
+        calls.fetch_add(1, Ordering::SeqCst);
+        Thread::current().name().is_none()
+    });
+    assert!(unnamed.iter().all(|r| r.as_ref().ok() == Some(&true)));
+    assert_eq!(calls.load(Ordering::SeqCst), 5);
+
+    // a panic only affects its own slot
+    let results = spawn_collect!(vec![1, 0, 2], |d: i32| {
+        if d == 0 { panic!("division by zero") }
+        10 / d
+    });
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0].as_ref().ok(), Some(&10));
+    assert_eq!(results[2].as_ref().ok(), Some(&5));
+    let payload = results.into_iter().nth(1).unwrap().err().unwrap();
+    assert_eq!(*payload.downcast::<&'static str>().ok().unwrap(), "division by zero");
+
+    let none: Vec<Result<(), _>> = spawn_collect!(Vec::<u8>::new(), |_: u8| ());
+    assert!(none.is_empty());
+}
@@ -0,0 +1,31 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::boxed::BoxAny;
+
+fn main() {
+    let v = vec![1, 2, 3];
+    let guard = spawn_on_cpu!(0, {
+        v.iter().fold(0, |a, b| a + *b)
+    });
+    assert_eq!(guard.join().ok(), Some(6));
+
+    // a CPU which can't exist still runs the block
+    let guard = spawn_on_cpu!(1 << 20, { "unpinned" });
+    assert_eq!(guard.join().ok(), Some("unpinned"));
+
+    // panics propagate to the joiner
+    let guard = spawn_on_cpu!(0, {
+        if true { panic!("on cpu 0") }
+        0
+    });
+    let err = guard.join().err().unwrap();
+    assert_eq!(*err.downcast::<&'static str>().ok().unwrap(), "on cpu 0");
+}
@@ -0,0 +1,50 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::cell::Cell;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::Thread;
+
+fn main() {
+    // another thread makes the condition true partway through
+    let started = Arc::new(AtomicBool::new(false));
+    let ready = Arc::new(AtomicBool::new(false));
+    let (s, r) = (started.clone(), ready.clone());
+    let t = Thread::scoped(move|| {
+        while !s.load(Ordering::SeqCst) {}
+        r.store(true, Ordering::SeqCst);
+    });
+    let spins = Cell::new(0);
+    let ok = spin_until!(1_000_000_000, || {
+        spins.set(spins.get() + 1);
+        if spins.get() == 10 {
+            started.store(true, Ordering::SeqCst);
+        }
+        ready.load(Ordering::SeqCst)
+    });
+    assert!(ok);
+    assert!(spins.get() >= 10);
+    assert!(t.join().is_ok());
+
+    // never true: the closure is called once per spin, and then we give up
+    let spins = Cell::new(0);
+    let ok = spin_until!(100, || {
+        spins.set(spins.get() + 1);
+        false
+    });
+    assert!(!ok);
+    assert_eq!(spins.get(), 100);
+
+    // true straight away
+    let spins = Cell::new(0);
+    assert!(spin_until!(100, || { spins.set(spins.get() + 1); true }));
+    assert_eq!(spins.get(), 1);
+}
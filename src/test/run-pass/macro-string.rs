@@ -0,0 +1,56 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::cell::Cell;
+use std::fmt;
+
+// Displays the number of times it has been displayed.
+struct Counted(Cell<u32>);
+
+impl fmt::Display for Counted {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.set(self.0.get() + 1);
+        write!(f, "{}", self.0.get())
+    }
+}
+
+fn main() {
+    let owned = "owned".to_string();
+    let s = string!("x = ", 1.5f64, ", ", owned, ' ', -3, true,);
+    assert_eq!(s, "x = 1.5, owned -3true");
+    // the arguments are only borrowed
+    assert_eq!(owned, "owned");
+
+    // the lengths of string-like arguments are reserved up front, so a string
+    // made only of those is allocated at its final size
+    let unicode = string!("ü", 'ß', "→", owned, &owned);
+    assert_eq!(unicode, "üß→ownedowned");
+    assert_eq!(unicode.capacity(), unicode.len());
+    let borrowed = string!(&owned[1..], &owned[..1]);
+    assert_eq!(borrowed, "wnedo");
+    assert_eq!(borrowed.capacity(), borrowed.len());
+
+    // each argument is evaluated and formatted once
+    let c = Counted(Cell::new(0));
+    assert_eq!(string!(c, "-", c), "1-2");
+    assert_eq!(c.0.get(), 2);
+    let calls = Cell::new(0);
+    let s = string!({ calls.set(calls.get() + 1); "once" }, '!');
+    assert_eq!((&s[], calls.get()), ("once!", 1));
+
+    assert_eq!(string!(7), "7");
+    assert_eq!(string!(), "");
+
+    let mut buf = string_cap!(32);
+    assert!(buf.capacity() >= 32);
+    assert!(buf.is_empty());
+    buf.push_str("fits without growing");
+    assert!(buf.capacity() >= 32);
+}
@@ -0,0 +1,49 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::cell::RefCell;
+
+struct Config {
+    name: String,
+    level: u32,
+    verbose: bool,
+}
+
+fn log<T>(order: &RefCell<Vec<&'static str>>, what: &'static str, x: T) -> T {
+    order.borrow_mut().push(what);
+    x
+}
+
+fn tweak(c: &mut Config) {
+    struct_update!(*c, { level: c.level + 1, verbose: true, });
+}
+
+fn main() {
+    let mut c = Config { name: "a".to_string(), level: 1, verbose: false };
+
+    // two fields, with values evaluated in the order they're listed
+    let order = RefCell::new(Vec::new());
+    struct_update!(c, {
+        level: log(&order, "level", 5),
+        name: log(&order, "name", "b".to_string())
+    });
+    assert_eq!(*order.borrow(), vec!["level", "name"]);
+    assert_eq!(c.name, "b");
+    assert_eq!(c.level, 5);
+    assert!(!c.verbose);
+
+    // through a &mut, and with a value reading a field
+    tweak(&mut c);
+    assert_eq!(c.level, 6);
+    assert!(c.verbose);
+
+    struct_update!(c, {});
+    assert_eq!(c.level, 6);
+}
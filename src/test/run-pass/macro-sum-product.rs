@@ -0,0 +1,51 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::cell::Cell;
+use std::ops::{Add, Mul};
+
+#[derive(Copy, PartialEq, Debug)]
+struct V2(i32, i32);
+
+impl Add for V2 {
+    type Output = V2;
+    fn add(self, other: V2) -> V2 { V2(self.0 + other.0, self.1 + other.1) }
+}
+
+impl Mul for V2 {
+    type Output = V2;
+    fn mul(self, other: V2) -> V2 { V2(self.0 * other.0, self.1 * other.1) }
+}
+
+fn counted(counter: &Cell<u32>, x: i32) -> i32 {
+    counter.set(counter.get() + 1);
+    x
+}
+
+fn main() {
+    assert_eq!(sum!(1, 2, 3), 6);
+    assert_eq!(sum!(7), 7);
+    assert_eq!(sum!(1u8, 2, 3,), 6);
+    assert_eq!(product!(2, 3, 4), 24);
+    assert_eq!(product!(-5i64), -5);
+
+    assert_eq!(sum!(0.5f64, 0.25, 0.125), 0.875);
+    assert_eq!(product!(1.5f32, 2.0), 3.0);
+
+    assert_eq!(sum!(V2(1, 2), V2(3, 4), V2(5, 6)), V2(9, 12));
+    assert_eq!(product!(V2(1, 2), V2(3, 4)), V2(3, 8));
+
+    let n = Cell::new(0);
+    assert_eq!(sum!(counted(&n, 1), counted(&n, 2), counted(&n, 3)), 6);
+    assert_eq!(n.get(), 3);
+    n.set(0);
+    assert_eq!(product!(counted(&n, 2), counted(&n, 5)), 10);
+    assert_eq!(n.get(), 2);
+}
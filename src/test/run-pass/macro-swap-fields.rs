@@ -0,0 +1,52 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::cell::Cell;
+
+struct State {
+    current: String,
+    previous: String,
+    ticks: u32,
+    limit: u32,
+}
+
+impl State {
+    fn rotate(&mut self, next: &str) {
+        swap_fields!(*self, current, previous);
+        self.current = next.to_string();
+    }
+}
+
+fn counted<'a>(calls: &Cell<u32>, s: &'a mut State) -> &'a mut State {
+    calls.set(calls.get() + 1);
+    s
+}
+
+fn main() {
+    let mut s = State {
+        current: "b".to_string(),
+        previous: "a".to_string(),
+        ticks: 1,
+        limit: 10,
+    };
+    swap_fields!(s, current, previous);
+    assert_eq!((&s.current[], &s.previous[]), ("a", "b"));
+    swap_fields!(s, ticks, limit);
+    assert_eq!((s.ticks, s.limit), (10, 1));
+
+    s.rotate("c");
+    assert_eq!((&s.current[], &s.previous[]), ("c", "a"));
+
+    // the object expression is only evaluated once
+    let calls = Cell::new(0);
+    swap_fields!(*counted(&calls, &mut s), ticks, limit);
+    assert_eq!(calls.get(), 1);
+    assert_eq!((s.ticks, s.limit), (1, 10));
+}
@@ -0,0 +1,57 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#[derive(Default)]
+struct Batch {
+    items: Vec<String>,
+    label: String,
+    count: u32,
+}
+
+fn flush(batch: &mut Batch) -> Vec<String> {
+    take_vec!(batch.items)
+}
+
+fn main() {
+    let mut v = vec![1, 2, 3];
+    let old = take_vec!(v);
+    assert_eq!(old, vec![1, 2, 3]);
+    assert!(v.is_empty());
+    // and the place can be used again
+    v.push(4);
+    assert_eq!(v, vec![4]);
+
+    let mut batch = Batch {
+        items: vec!["a".to_string(), "b".to_string()],
+        label: "first".to_string(),
+        count: 2,
+    };
+    assert_eq!(flush(&mut batch), vec!["a".to_string(), "b".to_string()]);
+    assert!(batch.items.is_empty());
+    assert!(flush(&mut batch).is_empty());
+
+    // any Default type
+    assert_eq!(take!(batch.label), "first");
+    assert_eq!(batch.label, "");
+    assert_eq!(take!(batch.count), 2);
+    assert_eq!(batch.count, 0);
+
+    let mut o = Some(5);
+    assert_eq!(take!(o), Some(5));
+    assert_eq!(o, None);
+
+    {
+        let r = &mut batch;
+        r.label.push_str("second");
+        let taken: Batch = take!(*r);
+        assert_eq!(taken.label, "second");
+    }
+    assert_eq!(batch.label, "");
+}
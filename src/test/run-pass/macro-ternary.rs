@@ -0,0 +1,29 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::cell::Cell;
+
+fn main() {
+    assert_eq!(ternary!(1 < 2, "yes", "no"), "yes");
+    assert_eq!(ternary!(1 > 2, "yes", "no"), "no");
+    let x = -4i32;
+    assert_eq!(ternary!(x < 0, -x, x) + 1, 5);
+
+    // only the chosen branch is evaluated
+    let (a, b) = (Cell::new(0), Cell::new(0));
+    let choose = |flag| ternary!(flag, { a.set(a.get() + 1); 'a' }, { b.set(b.get() + 1); 'b' });
+    assert_eq!(choose(true), 'a');
+    assert_eq!((a.get(), b.get()), (1, 0));
+    assert_eq!(choose(false), 'b');
+    assert_eq!((a.get(), b.get()), (1, 1));
+
+    let v: Vec<i32> = vec![];
+    assert_eq!(ternary!(v.is_empty(), 0, v[0]), 0);
+}
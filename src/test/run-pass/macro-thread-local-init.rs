@@ -0,0 +1,48 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::cell::Cell;
+use std::sync::atomic::{AtomicUsize, Ordering, ATOMIC_USIZE_INIT};
+use std::thread::Thread;
+
+static INITS: AtomicUsize = ATOMIC_USIZE_INIT;
+
+fn init() -> Cell<u32> {
+    INITS.fetch_add(1, Ordering::SeqCst);
+    Cell::new(0)
+}
+
+thread_local_init!(static COUNTER: Cell<u32> = init();)
+
+fn bump(n: u32) -> u32 {
+    for _ in 0..n {
+        COUNTER.with(|c| c.set(c.get() + 1));
+    }
+    COUNTER.with(|c| c.get())
+}
+
+fn main() {
+    // nothing runs until the key is first used
+    assert_eq!(INITS.load(Ordering::SeqCst), 0);
+    assert_eq!(bump(2), 2);
+    assert_eq!(INITS.load(Ordering::SeqCst), 1);
+    assert_eq!(bump(1), 3);
+    assert_eq!(INITS.load(Ordering::SeqCst), 1);
+
+    // each thread starts from its own value, initialized once
+    let a = Thread::scoped(move|| bump(5));
+    let b = Thread::scoped(move|| { bump(3); bump(4) });
+    assert_eq!(a.join().ok().unwrap(), 5);
+    assert_eq!(b.join().ok().unwrap(), 7);
+    assert_eq!(INITS.load(Ordering::SeqCst), 3);
+
+    // and the main thread's value is unaffected
+    assert_eq!(bump(0), 3);
+}
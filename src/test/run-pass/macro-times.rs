@@ -0,0 +1,32 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+fn main() {
+    let mut runs = 0;
+    for _ in times!(5) {
+        runs += 1;
+    }
+    assert_eq!(runs, 5);
+
+    let n = 0us;
+    for _ in times!(n) {
+        panic!("ran for zero times");
+    }
+    assert_eq!(times!(4u8).collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+
+    let squares = times!(6, |i| i * i).collect::<Vec<u32>>();
+    assert_eq!(squares, vec![0, 1, 4, 9, 16, 25]);
+
+    // the mapping function may have state
+    let mut seen = vec![];
+    let labels = times!(3, |i| { seen.push(i); format!("#{}", i) }).collect::<Vec<_>>();
+    assert_eq!(labels, vec!["#0", "#1", "#2"]);
+    assert_eq!(seen, vec![0, 1, 2]);
+}
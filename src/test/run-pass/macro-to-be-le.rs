@@ -0,0 +1,42 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::cell::Cell;
+use std::num::Int;
+
+fn counted<T>(counter: &Cell<u32>, x: T) -> T {
+    counter.set(counter.get() + 1);
+    x
+}
+
+fn main() {
+    let (a, b, c) = (0x1234u16, 0x12345678u32, 0x0102030405060708u64);
+
+    let (ba, bb, bc) = to_be!(a, b, c);
+    assert_eq!(ba, a.to_be());
+    assert_eq!(bb, b.to_be());
+    assert_eq!(bc, c.to_be());
+    assert_eq!((Int::from_be(ba), Int::from_be(bb), Int::from_be(bc)), (a, b, c));
+
+    let (la, lb, lc) = to_le!(a, b, c,);
+    assert_eq!((Int::from_le(la), Int::from_le(lb), Int::from_le(lc)), (a, b, c));
+
+    // the byte order really is big/little endian
+    let (be,) = to_be!(0x0102u16);
+    let be: [u8; 2] = unsafe { std::mem::transmute(be) };
+    assert_eq!(be, [1, 2]);
+    let (le,) = to_le!(0x0102u16);
+    let le: [u8; 2] = unsafe { std::mem::transmute(le) };
+    assert_eq!(le, [2, 1]);
+
+    let n = Cell::new(0);
+    let _ = to_be!(counted(&n, 1u8), counted(&n, -2i32));
+    assert_eq!(n.get(), 2);
+}
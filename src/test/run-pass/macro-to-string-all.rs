@@ -0,0 +1,33 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::cell::Cell;
+
+fn main() {
+    let (i, f, s) = to_string_all!(-12, 2.5f64, "text");
+    assert_eq!(i, "-12");
+    assert_eq!(f, "2.5");
+    assert_eq!(s, "text");
+
+    let owned = String::from_str("kept");
+    let (a, b) = to_string_all!(owned, 'c',);
+    assert_eq!((&a[], &b[]), ("kept", "c"));
+    // the arguments are only borrowed
+    assert_eq!(owned, "kept");
+
+    let single: (String,) = to_string_all!(true);
+    assert_eq!(single.0, "true");
+
+    // each argument is evaluated once, in order
+    let n = Cell::new(0);
+    let next = || { n.set(n.get() + 1); n.get() };
+    assert_eq!(to_string_all!(next(), next(), next()),
+               ("1".to_string(), "2".to_string(), "3".to_string()));
+}
@@ -0,0 +1,30 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::cell::Cell;
+
+fn main() {
+    // all Ok
+    let v: Result<Vec<i32>, String> = try_collect!(vec![Ok(3), Ok(1), Ok(2)]);
+    let v = v.unwrap();
+    assert_eq!(v, vec![3, 1, 2]);
+    assert_eq!(v.len(), 3);
+    assert!(v.capacity() >= 3);
+
+    let empty: Result<Vec<u8>, ()> = try_collect!(Vec::new());
+    assert_eq!(empty, Ok(vec![]));
+
+    // the first Err is returned, and nothing after it is taken
+    let taken = Cell::new(0);
+    let items = vec![Ok(1), Ok(2), Err("bad"), Ok(4), Err("worse")];
+    let r = try_collect!(items.into_iter().map(|x| { taken.set(taken.get() + 1); x }));
+    assert_eq!(r, Err("bad"));
+    assert_eq!(taken.get(), 3);
+}
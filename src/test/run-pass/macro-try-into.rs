@@ -0,0 +1,40 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::{i8, i64, u8, u64};
+
+fn main() {
+    // with a fallback
+    assert_eq!(try_into_or!(42i64, 0u8), 42u8);
+    assert_eq!(try_into_or!(256i64, 7u8), 7u8);
+    assert_eq!(try_into_or!(-1i64, u8::MAX), u8::MAX);
+    assert_eq!(try_into_or!(u64::MAX, -1i64), -1);
+
+    // the fallback is only evaluated when it's needed
+    let mut fallbacks = 0;
+    assert_eq!(try_into_or!(5i64, { fallbacks += 1; 0u8 }), 5);
+    assert_eq!(fallbacks, 0);
+    assert_eq!(try_into_or!(500i64, { fallbacks += 1; 0u8 }), 0);
+    assert_eq!(fallbacks, 1);
+
+    // clamped to the target type's range
+    let in_range: u8 = try_into_clamp!(42i64);
+    assert_eq!(in_range, 42);
+    let high: u8 = try_into_clamp!(1000i64);
+    assert_eq!(high, u8::MAX);
+    let low: u8 = try_into_clamp!(-1000i64);
+    assert_eq!(low, 0);
+
+    assert_eq!(try_into_clamp!(i64::MIN, i8), i8::MIN);
+    assert_eq!(try_into_clamp!(i64::MAX, i8), i8::MAX);
+    assert_eq!(try_into_clamp!(u64::MAX, i64), i64::MAX);
+    assert_eq!(try_into_clamp!(-3i8, u64), 0);
+    assert_eq!(try_into_clamp!(-3i8, i64), -3);
+}
@@ -0,0 +1,49 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::sync::{Arc, Mutex};
+use std::sync::mpsc::channel;
+use std::thread::Thread;
+
+fn increment(m: &Mutex<i32>) -> bool {
+    let mut n = try_lock!(m, else return false);
+    *n += 1;
+    true
+}
+
+fn main() {
+    // uncontended
+    let m = Arc::new(Mutex::new(0));
+    {
+        let mut guard = try_lock!(m).unwrap();
+        *guard += 1;
+    }
+    assert!(increment(&*m));
+    assert_eq!(*m.lock().unwrap(), 2);
+
+    // held by another thread
+    let (locked_tx, locked_rx) = channel();
+    let (release_tx, release_rx) = channel::<()>();
+    let m2 = m.clone();
+    let t = Thread::scoped(move|| {
+        let _guard = m2.lock().unwrap();
+        locked_tx.send(()).unwrap();
+        release_rx.recv().unwrap();
+    });
+    locked_rx.recv().unwrap();
+    assert!(try_lock!(m).is_none());
+    assert!(!increment(&*m));
+    release_tx.send(()).unwrap();
+    assert!(t.join().is_ok());
+
+    // and free again once it's released
+    assert!(increment(&*m));
+    assert_eq!(*try_lock!(m, else panic!("the lock should be free")), 3);
+}
@@ -0,0 +1,49 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::cell::Cell;
+
+fn counted<T>(calls: &Cell<u32>, x: T) -> T {
+    calls.set(calls.get() + 1);
+    x
+}
+
+fn main() {
+    let calls = Cell::new(0);
+
+    // everything present
+    let all = unwrap_all!(counted(&calls, Some(1)),
+                          counted(&calls, Some("two")),
+                          counted(&calls, Some(3.0f64)));
+    assert_eq!(all, Some((1, "two", 3.0)));
+    assert_eq!(calls.get(), 3);
+    assert_eq!(unwrap_all!(Some('x'),), Some(('x',)));
+
+    // a missing value stops evaluation
+    calls.set(0);
+    let missing = unwrap_all!(counted(&calls, Some(1)),
+                              counted(&calls, None::<u8>),
+                              counted(&calls, Some(3)));
+    assert_eq!(missing, None);
+    assert_eq!(calls.get(), 2);
+
+    // results give the first error
+    calls.set(0);
+    let ok: Result<_, &str> = unwrap_all_ok!(counted(&calls, Ok(1)), counted(&calls, Ok('b')));
+    assert_eq!(ok, Ok((1, 'b')));
+    assert_eq!(calls.get(), 2);
+
+    calls.set(0);
+    let err: Result<(u8, u8, u8), &str> = unwrap_all_ok!(counted(&calls, Ok(1)),
+                                                         counted(&calls, Err("second")),
+                                                         counted(&calls, Err("third")));
+    assert_eq!(err, Err("second"));
+    assert_eq!(calls.get(), 2);
+}
@@ -0,0 +1,51 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::sync::{Arc, Mutex, TryLockError};
+use std::thread::Thread;
+
+fn main() {
+    let counter = Arc::new(Mutex::new(0u32));
+    let threads = (0..8).map(|_| {
+        let counter = counter.clone();
+        Thread::scoped(move|| {
+            for _ in 0..100 {
+                with_lock!(counter, |n| **n += 1);
+            }
+        })
+    }).collect::<Vec<_>>();
+    for t in threads {
+        assert!(t.join().is_ok());
+    }
+    assert_eq!(with_lock!(counter, |n| **n), 800);
+
+    // the lock is released as soon as the closure returns
+    let words = Mutex::new(vec!["a"]);
+    let len = with_lock!(words, |w| {
+        w.push("b");
+        assert!(words.try_lock().is_err());
+        w.len()
+    });
+    assert_eq!(len, 2);
+    assert!(words.try_lock().is_ok());
+    assert_eq!(*words.lock().unwrap(), vec!["a", "b"]);
+
+    // including when it panics, which poisons the mutex
+    let shared = Arc::new(Mutex::new(1));
+    let s = shared.clone();
+    let r = Thread::scoped(move|| with_lock!(s, |_v| if true { panic!("inside the lock") })).join();
+    assert!(r.is_err());
+    match shared.try_lock() {
+        Err(TryLockError::Poisoned(..)) => {}
+        _ => panic!("the lock should be free but poisoned"),
+    }
+    let poisoned = Thread::scoped(move|| with_lock!(shared, |v| **v)).join();
+    assert!(poisoned.is_err());
+}
@@ -0,0 +1,33 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::old_io::timer;
+use std::sync::mpsc::channel;
+use std::time::Duration;
+
+fn main() {
+    // a fast closure finishes in time
+    let v = with_timeout!(Duration::seconds(10), || vec![1, 2, 3]);
+    assert_eq!(v, Some(vec![1, 2, 3]));
+
+    // a slow one does not, but keeps running afterwards
+    let (tx, rx) = channel();
+    let v = with_timeout!(Duration::milliseconds(10), move|| {
+        timer::sleep(Duration::milliseconds(500));
+        tx.send(()).unwrap();
+        "too late"
+    });
+    assert_eq!(v, None);
+    rx.recv().unwrap();
+
+    // as does one which panics
+    let v: Option<i32> = with_timeout!(Duration::seconds(10), || panic!());
+    assert_eq!(v, None);
+}
@@ -0,0 +1,41 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::cell::RefCell;
+use std::{i32, u64};
+
+fn main() {
+    // nothing overflows, so this is ordinary arithmetic
+    let (a, b, c) = (2i32, 3i32, 4i32);
+    assert_eq!(wrapping!(a + b * c), a + b * c);
+    assert_eq!(wrapping!(a * b + c), a * b + c);
+    assert_eq!(wrapping!(a - b - c), a - b - c);
+    assert_eq!(wrapping!((a - b) * c), (a - b) * c);
+    assert_eq!(wrapping!(a * (b + c) * 2 - 1), a * (b + c) * 2 - 1);
+    assert_eq!(wrapping!(7), 7);
+
+    // wrapping around the bounds
+    assert_eq!(wrapping!(250u8 + 10), 4);
+    assert_eq!(wrapping!(3u8 - 5), 254);
+    assert_eq!(wrapping!(16u8 * 17), 16);
+    assert_eq!(wrapping!(i32::MAX + 1), i32::MIN);
+    assert_eq!(wrapping!(i32::MIN - 1), i32::MAX);
+    assert_eq!(wrapping!(std::i32::MAX * 2 + (i32::MIN + 2)), i32::MIN);
+    let max = u64::MAX;
+    assert_eq!(wrapping!(max * max), 1);
+    assert_eq!(wrapping!(max + max * 2 + 3), 0);
+
+    // operands in blocks are evaluated once each, from left to right
+    let order = RefCell::new(vec![]);
+    let next = |n: u8| { order.borrow_mut().push(n); n };
+    let v = wrapping!({ next(100) } * { next(3) } + { next(7) } - { next(1) });
+    assert_eq!(v, 50);
+    assert_eq!(*order.borrow(), vec![100, 3, 7, 1]);
+}
@@ -0,0 +1,30 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+fn main() {
+    // equal lengths
+    let names = vec!["a", "b", "c"];
+    let ages = vec![1u8, 2, 3];
+    let flags = vec![true, false, true];
+    let v: Vec<(&str, u8, bool)> = zip_collect!(names.clone(), ages, flags);
+    assert_eq!(v, vec![("a", 1, true), ("b", 2, false), ("c", 3, true)]);
+
+    // unequal lengths stop at the shortest, wherever it is
+    let v = zip_collect!(0..10, names.iter(), "xy".chars());
+    assert_eq!(v, vec![(0, &"a", 'x'), (1, &"b", 'y')]);
+    let v = zip_collect!(vec![1, 2], 0..5, 10..20, "abc".chars(),);
+    assert_eq!(v, vec![(1, 0, 10, 'a'), (2, 1, 11, 'b')]);
+
+    let v = zip_collect!(0..3, Vec::<i32>::new(), 0..3);
+    assert!(v.is_empty());
+
+    let v = zip_collect!(0..2, 2..4);
+    assert_eq!(v, vec![(0, 2), (1, 3)]);
+}